@@ -0,0 +1,684 @@
+use crate::hashmap::HashMap;
+use crate::linked_hashmap::LinkedHashMap;
+use std::borrow::Borrow;
+use std::hash::Hash;
+
+type EvictionCallback<K, V> = Box<dyn FnMut(K, V)>;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    pub evictions: u64,
+}
+
+/// Bounded cache that evicts the least recently used entry once it is full.
+pub struct LruCache<K: Hash + PartialEq, V> {
+    map: LinkedHashMap<K, V>,
+    capacity: usize,
+    stats: CacheStats,
+    on_evict: Option<EvictionCallback<K, V>>,
+}
+
+impl<K: Hash + PartialEq, V> LruCache<K, V> {
+    /// Panics if `capacity` is zero.
+    pub fn new(capacity: usize) -> Self {
+        assert!(capacity > 0, "Cache capacity must be greater than zero");
+        LruCache {
+            map: LinkedHashMap::with_access_order(),
+            capacity,
+            stats: CacheStats::default(),
+            on_evict: None,
+        }
+    }
+
+    /// Registers a callback that receives every entry evicted to make room.
+    /// Entries removed with `pop` or `clear` are not passed to it.
+    pub fn on_evict<F: FnMut(K, V) + 'static>(&mut self, callback: F) {
+        self.on_evict = Some(Box::new(callback));
+    }
+
+    /// Returns the value and marks it as the most recently used one.
+    pub fn get<Q>(&mut self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + PartialEq + ?Sized,
+    {
+        self.get_mut(key).map(|value| &*value)
+    }
+
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + PartialEq + ?Sized,
+    {
+        let value = self.map.get_mut(key);
+        if value.is_some() {
+            self.stats.hits += 1;
+        } else {
+            self.stats.misses += 1;
+        }
+        value
+    }
+
+    /// Returns the value without touching its recency or the hit and miss counters.
+    pub fn peek<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + PartialEq + ?Sized,
+    {
+        self.map.peek(key)
+    }
+
+    pub fn contains<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + PartialEq + ?Sized,
+    {
+        self.map.contains_key(key)
+    }
+
+    /// Inserts or updates an entry, evicting the least recently used one if the cache is full.
+    pub fn put(&mut self, key: K, value: V) {
+        if !self.map.contains_key(&key) && self.map.len() == self.capacity {
+            self.evict();
+        }
+        self.map.put(key, value);
+    }
+
+    pub fn pop<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + PartialEq + ?Sized,
+    {
+        self.map.remove(key)
+    }
+
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Changes the capacity, evicting least recently used entries right away if the
+    /// cache holds more than the new capacity. Panics if `capacity` is zero.
+    pub fn set_capacity(&mut self, capacity: usize) {
+        assert!(capacity > 0, "Cache capacity must be greater than zero");
+        self.capacity = capacity;
+        while self.map.len() > capacity {
+            self.evict();
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.map.clear();
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        self.stats
+    }
+
+    /// Iterates from the least to the most recently used entry.
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> {
+        self.map.iter()
+    }
+
+    fn evict(&mut self) {
+        if let Some((key, value)) = self.map.pop_front() {
+            self.stats.evictions += 1;
+            if let Some(callback) = self.on_evict.as_mut() {
+                callback(key, value);
+            }
+        }
+    }
+}
+
+/// Bounded cache that evicts the least frequently used entry once it is full.
+/// Ties between entries with the same use count go to the least recently used one.
+///
+/// Entries with the same use count form a group, kept as a list from least to most
+/// recently used, and the groups form a list ordered by use count. Entries and groups
+/// live in slabs and are linked by index, so every operation is O(1).
+pub struct LfuCache<K: Hash + PartialEq + Clone, V> {
+    index: HashMap<K, usize>,
+    entries: Vec<Option<LfuEntry<K, V>>>,
+    free_entries: Vec<usize>,
+    groups: Vec<Option<FrequencyGroup>>,
+    free_groups: Vec<usize>,
+    // The group with the lowest use count
+    lowest: Option<usize>,
+    capacity: usize,
+    stats: CacheStats,
+    on_evict: Option<EvictionCallback<K, V>>,
+}
+
+struct LfuEntry<K, V> {
+    key: K,
+    value: V,
+    group: usize,
+    prev: Option<usize>,
+    next: Option<usize>,
+}
+
+// Groups are removed as soon as they become empty
+struct FrequencyGroup {
+    frequency: usize,
+    first: Option<usize>,
+    last: Option<usize>,
+    prev: Option<usize>,
+    next: Option<usize>,
+}
+
+impl<K: Hash + PartialEq + Clone, V> LfuCache<K, V> {
+    /// Panics if `capacity` is zero.
+    pub fn new(capacity: usize) -> Self {
+        assert!(capacity > 0, "Cache capacity must be greater than zero");
+        LfuCache {
+            index: HashMap::with_capacity(capacity),
+            entries: Vec::with_capacity(capacity),
+            free_entries: Vec::new(),
+            groups: Vec::new(),
+            free_groups: Vec::new(),
+            lowest: None,
+            capacity,
+            stats: CacheStats::default(),
+            on_evict: None,
+        }
+    }
+
+    /// Registers a callback that receives every entry evicted to make room.
+    /// Entries removed with `pop` or `clear` are not passed to it.
+    pub fn on_evict<F: FnMut(K, V) + 'static>(&mut self, callback: F) {
+        self.on_evict = Some(Box::new(callback));
+    }
+
+    /// Returns the value and counts it as one more use.
+    pub fn get<Q>(&mut self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + PartialEq + ?Sized,
+    {
+        self.get_mut(key).map(|value| &*value)
+    }
+
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + PartialEq + ?Sized,
+    {
+        let slot = match self.index.get(key) {
+            Some(&slot) => slot,
+            None => {
+                self.stats.misses += 1;
+                return None;
+            }
+        };
+        self.stats.hits += 1;
+        self.touch(slot);
+        Some(&mut self.entry_mut(slot).value)
+    }
+
+    /// Returns the value without touching its use count or the hit and miss counters.
+    pub fn peek<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + PartialEq + ?Sized,
+    {
+        self.index.get(key).map(|&slot| &self.entry(slot).value)
+    }
+
+    pub fn contains<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + PartialEq + ?Sized,
+    {
+        self.index.contains_key(key)
+    }
+
+    /// Use count of an entry, starting at 1 when it is inserted.
+    pub fn frequency<Q>(&self, key: &Q) -> Option<usize>
+    where
+        K: Borrow<Q>,
+        Q: Hash + PartialEq + ?Sized,
+    {
+        let &slot = self.index.get(key)?;
+        Some(self.group(self.entry(slot).group).frequency)
+    }
+
+    /// Inserts or updates an entry, evicting the least frequently used one if the cache
+    /// is full. Updating an entry counts as a use.
+    pub fn put(&mut self, key: K, value: V) {
+        if let Some(&slot) = self.index.get(&key) {
+            self.entry_mut(slot).value = value;
+            self.touch(slot);
+            return;
+        }
+        if self.index.len() == self.capacity {
+            self.evict();
+        }
+        // A new entry always has the lowest use count
+        let group = match self.lowest {
+            Some(lowest) if self.group(lowest).frequency == 1 => lowest,
+            lowest => self.insert_group(1, None, lowest),
+        };
+        let entry = LfuEntry {
+            key: key.clone(),
+            value,
+            group,
+            prev: None,
+            next: None,
+        };
+        let slot = allocate(&mut self.entries, &mut self.free_entries, entry);
+        self.push_back(group, slot);
+        self.index.put(key, slot);
+    }
+
+    pub fn pop<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + PartialEq + ?Sized,
+    {
+        let slot = self.index.remove(key)?;
+        Some(self.release(slot).value)
+    }
+
+    pub fn len(&self) -> usize {
+        self.index.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.index.is_empty()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Changes the capacity, evicting least frequently used entries right away if the
+    /// cache holds more than the new capacity. Panics if `capacity` is zero.
+    pub fn set_capacity(&mut self, capacity: usize) {
+        assert!(capacity > 0, "Cache capacity must be greater than zero");
+        self.capacity = capacity;
+        while self.index.len() > capacity {
+            self.evict();
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.index.clear();
+        self.entries.clear();
+        self.free_entries.clear();
+        self.groups.clear();
+        self.free_groups.clear();
+        self.lowest = None;
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        self.stats
+    }
+
+    /// Moves an entry from its current use count group to the back of the next one.
+    fn touch(&mut self, slot: usize) {
+        let group = self.entry(slot).group;
+        let frequency = self.group(group).frequency + 1;
+        let target = match self.group(group).next {
+            Some(next) if self.group(next).frequency == frequency => next,
+            next => self.insert_group(frequency, Some(group), next),
+        };
+        self.unlink(slot);
+        self.push_back(target, slot);
+    }
+
+    fn evict(&mut self) {
+        let slot = match self.lowest {
+            Some(lowest) => self.group(lowest).first.unwrap(),
+            None => return,
+        };
+        let entry = self.release(slot);
+        self.index.remove(&entry.key);
+        self.stats.evictions += 1;
+        if let Some(callback) = self.on_evict.as_mut() {
+            callback(entry.key, entry.value);
+        }
+    }
+
+    fn release(&mut self, slot: usize) -> LfuEntry<K, V> {
+        self.unlink(slot);
+        self.free_entries.push(slot);
+        self.entries[slot].take().unwrap()
+    }
+
+    fn push_back(&mut self, group: usize, slot: usize) {
+        let last = self.group(group).last;
+        let entry = self.entry_mut(slot);
+        entry.group = group;
+        entry.prev = last;
+        entry.next = None;
+        match last {
+            Some(last) => self.entry_mut(last).next = Some(slot),
+            None => self.group_mut(group).first = Some(slot),
+        }
+        self.group_mut(group).last = Some(slot);
+    }
+
+    // Takes an entry out of its group, dropping the group if it becomes empty
+    fn unlink(&mut self, slot: usize) {
+        let entry = self.entry_mut(slot);
+        let (group, prev, next) = (entry.group, entry.prev.take(), entry.next.take());
+        match prev {
+            Some(prev) => self.entry_mut(prev).next = next,
+            None => self.group_mut(group).first = next,
+        }
+        match next {
+            Some(next) => self.entry_mut(next).prev = prev,
+            None => self.group_mut(group).last = prev,
+        }
+        if self.group(group).first.is_none() {
+            self.remove_group(group);
+        }
+    }
+
+    fn insert_group(
+        &mut self,
+        frequency: usize,
+        prev: Option<usize>,
+        next: Option<usize>,
+    ) -> usize {
+        let group = FrequencyGroup {
+            frequency,
+            first: None,
+            last: None,
+            prev,
+            next,
+        };
+        let group = allocate(&mut self.groups, &mut self.free_groups, group);
+        match prev {
+            Some(prev) => self.group_mut(prev).next = Some(group),
+            None => self.lowest = Some(group),
+        }
+        if let Some(next) = next {
+            self.group_mut(next).prev = Some(group);
+        }
+        group
+    }
+
+    fn remove_group(&mut self, group: usize) {
+        let removed = self.groups[group].take().unwrap();
+        self.free_groups.push(group);
+        match removed.prev {
+            Some(prev) => self.group_mut(prev).next = removed.next,
+            None => self.lowest = removed.next,
+        }
+        if let Some(next) = removed.next {
+            self.group_mut(next).prev = removed.prev;
+        }
+    }
+
+    fn entry(&self, slot: usize) -> &LfuEntry<K, V> {
+        self.entries[slot].as_ref().unwrap()
+    }
+
+    fn entry_mut(&mut self, slot: usize) -> &mut LfuEntry<K, V> {
+        self.entries[slot].as_mut().unwrap()
+    }
+
+    fn group(&self, group: usize) -> &FrequencyGroup {
+        self.groups[group].as_ref().unwrap()
+    }
+
+    fn group_mut(&mut self, group: usize) -> &mut FrequencyGroup {
+        self.groups[group].as_mut().unwrap()
+    }
+}
+
+// Stores `item` in a freed slot if there is one, returning its index
+fn allocate<T>(slots: &mut Vec<Option<T>>, free: &mut Vec<usize>, item: T) -> usize {
+    match free.pop() {
+        Some(index) => {
+            slots[index] = Some(item);
+            index
+        }
+        None => {
+            slots.push(Some(item));
+            slots.len() - 1
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cache::{CacheStats, LfuCache, LruCache};
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    #[test]
+    fn lru_evicts_least_recently_used() {
+        let mut cache = LruCache::new(2);
+        cache.put("a", 1);
+        cache.put("b", 2);
+        assert_eq!(cache.get("a"), Some(&1));
+        cache.put("c", 3);
+        assert_eq!(cache.len(), 2);
+        assert!(cache.contains("a"));
+        assert!(!cache.contains("b"));
+        assert!(cache.contains("c"));
+        cache.put("a", 10);
+        cache.put("d", 4);
+        assert_eq!(cache.peek("a"), Some(&10));
+        assert_eq!(cache.peek("c"), None);
+    }
+
+    #[test]
+    fn lru_peek_does_not_refresh() {
+        let mut cache = LruCache::new(2);
+        cache.put(1, "one");
+        cache.put(2, "two");
+        assert_eq!(cache.peek(&1), Some(&"one"));
+        cache.put(3, "three");
+        assert_eq!(cache.peek(&1), None);
+        assert_eq!(
+            cache.iter().map(|(key, _)| *key).collect::<Vec<_>>(),
+            vec![2, 3]
+        );
+    }
+
+    #[test]
+    fn lru_stats() {
+        let mut cache = LruCache::new(1);
+        cache.put(1, 1);
+        cache.get(&1);
+        cache.get(&2);
+        *cache.get_mut(&1).unwrap() += 1;
+        cache.put(2, 2);
+        cache.peek(&3);
+        assert_eq!(
+            cache.stats(),
+            CacheStats {
+                hits: 2,
+                misses: 1,
+                evictions: 1,
+            }
+        );
+        assert_eq!(cache.pop(&2), Some(2));
+        assert!(cache.is_empty());
+        assert_eq!(cache.stats().evictions, 1);
+    }
+
+    #[test]
+    fn lru_eviction_callback() {
+        let evicted = Rc::new(RefCell::new(Vec::new()));
+        let mut cache = LruCache::new(3);
+        let sink = evicted.clone();
+        cache.on_evict(move |key, value| sink.borrow_mut().push((key, value)));
+        for i in 0..5 {
+            cache.put(i, i * 10);
+        }
+        assert_eq!(*evicted.borrow(), vec![(0, 0), (1, 10)]);
+        cache.pop(&4);
+        cache.clear();
+        assert_eq!(evicted.borrow().len(), 2);
+    }
+
+    #[test]
+    fn lru_set_capacity() {
+        let evicted = Rc::new(RefCell::new(Vec::new()));
+        let mut cache = LruCache::new(5);
+        let sink = evicted.clone();
+        cache.on_evict(move |key, _| sink.borrow_mut().push(key));
+        for i in 0..5 {
+            cache.put(i, i);
+        }
+        cache.get(&0);
+        cache.set_capacity(2);
+        assert_eq!(cache.capacity(), 2);
+        assert_eq!(cache.len(), 2);
+        assert_eq!(*evicted.borrow(), vec![1, 2, 3]);
+        assert!(cache.contains(&4));
+        assert!(cache.contains(&0));
+        cache.set_capacity(10);
+        for i in 10..18 {
+            cache.put(i, i);
+        }
+        assert_eq!(cache.len(), 10);
+        assert_eq!(cache.stats().evictions, 3);
+    }
+
+    #[test]
+    #[should_panic]
+    fn lru_zero_capacity() {
+        let _cache: LruCache<i32, i32> = LruCache::new(0);
+    }
+
+    #[test]
+    fn lfu_evicts_least_frequently_used() {
+        let mut cache = LfuCache::new(2);
+        cache.put("a", 1);
+        cache.put("b", 2);
+        assert_eq!(cache.get("a"), Some(&1));
+        assert_eq!(cache.get("a"), Some(&1));
+        assert_eq!(cache.get("b"), Some(&2));
+        cache.put("c", 3);
+        assert!(cache.contains("a"));
+        assert!(!cache.contains("b"));
+        assert!(cache.contains("c"));
+        assert_eq!(cache.frequency("a"), Some(3));
+        assert_eq!(cache.frequency("c"), Some(1));
+    }
+
+    #[test]
+    fn lfu_ties_go_to_least_recently_used() {
+        let mut cache = LfuCache::new(3);
+        cache.put(1, 1);
+        cache.put(2, 2);
+        cache.put(3, 3);
+        cache.get(&2);
+        cache.get(&1);
+        cache.get(&3);
+        // All three were used twice, and 2 is the one used longest ago
+        cache.put(4, 4);
+        assert!(!cache.contains(&2));
+        cache.put(5, 5);
+        // 4 was used once, so it goes before any of the others
+        assert!(!cache.contains(&4));
+        assert!(cache.contains(&1));
+        assert!(cache.contains(&3));
+        assert!(cache.contains(&5));
+    }
+
+    #[test]
+    fn lfu_put_existing_counts_as_use() {
+        let mut cache = LfuCache::new(2);
+        cache.put("a", 1);
+        cache.put("b", 2);
+        cache.put("a", 10);
+        assert_eq!(cache.frequency("a"), Some(2));
+        cache.put("c", 3);
+        assert_eq!(cache.peek("a"), Some(&10));
+        assert_eq!(cache.peek("b"), None);
+        assert_eq!(cache.frequency("a"), Some(2));
+    }
+
+    #[test]
+    fn lfu_pop_then_evict() {
+        let mut cache = LfuCache::new(2);
+        cache.put(1, 1);
+        cache.get(&1);
+        cache.put(2, 2);
+        assert_eq!(cache.pop(&2), Some(2));
+        cache.put(3, 3);
+        cache.get(&3);
+        cache.get(&3);
+        cache.put(4, 4);
+        // The cache was full with 1 (used twice) and 3 (used three times)
+        assert!(!cache.contains(&1));
+        assert!(cache.contains(&3));
+        assert!(cache.contains(&4));
+    }
+
+    #[test]
+    fn lfu_stats_and_callback() {
+        let evicted = Rc::new(RefCell::new(Vec::new()));
+        let mut cache = LfuCache::new(2);
+        let sink = evicted.clone();
+        cache.on_evict(move |key, value| sink.borrow_mut().push((key, value)));
+        cache.put(1, "one");
+        cache.put(2, "two");
+        cache.get(&1);
+        cache.get(&7);
+        cache.put(3, "three");
+        assert_eq!(*evicted.borrow(), vec![(2, "two")]);
+        assert_eq!(
+            cache.stats(),
+            CacheStats {
+                hits: 1,
+                misses: 1,
+                evictions: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn lfu_set_capacity() {
+        let mut cache = LfuCache::new(4);
+        for i in 0..4 {
+            cache.put(i, i);
+            for _ in 0..i {
+                cache.get(&i);
+            }
+        }
+        cache.set_capacity(2);
+        assert_eq!(cache.len(), 2);
+        assert!(cache.contains(&2));
+        assert!(cache.contains(&3));
+        assert_eq!(cache.stats().evictions, 2);
+        cache.clear();
+        assert!(cache.is_empty());
+        cache.put(9, 9);
+        assert_eq!(cache.get(&9), Some(&9));
+    }
+
+    #[test]
+    fn lfu_reuses_slots() {
+        let mut cache = LfuCache::new(2);
+        cache.put("hot", 0);
+        cache.put("cold", 0);
+        for round in 0..1000 {
+            *cache.get_mut("hot").unwrap() += 1;
+            cache.put("cold", round);
+        }
+        assert_eq!(cache.frequency("hot"), Some(1001));
+        assert_eq!(cache.frequency("cold"), Some(1001));
+        // Emptied groups and entries are recycled instead of piling up
+        assert!(cache.groups.len() <= 3);
+        assert_eq!(cache.entries.len(), 2);
+        cache.put("new", 0);
+        assert_eq!(cache.frequency("new"), Some(1));
+        assert_eq!(cache.len(), 2);
+    }
+}
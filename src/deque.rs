@@ -0,0 +1,715 @@
+use crate::queue_like::QueueLike;
+use crate::vector_based::VectorBasedDataStructure;
+use std::cmp::Ordering;
+use std::fmt::{self, Debug};
+use std::hash::{Hash, Hasher};
+use std::iter::FromIterator;
+use std::mem::MaybeUninit;
+use std::ops::{Index, IndexMut};
+use std::slice;
+
+const MIN_CAPACITY: usize = 4;
+
+/// Double-ended queue stored in a growable circular buffer.
+///
+/// The elements occupy `len` slots starting at `head` and wrapping around the end of
+/// `buffer`. Those slots are always initialized and every other slot is not, which is
+/// what the unsafe blocks below rely on.
+pub struct Deque<T> {
+    buffer: Vec<MaybeUninit<T>>,
+    head: usize,
+    len: usize,
+}
+
+impl<T> Deque<T> {
+    pub fn push_front(&mut self, element: T) {
+        self.grow_if_full();
+        self.head = self.wrap_sub(self.head, 1);
+        self.buffer[self.head] = MaybeUninit::new(element);
+        self.len += 1;
+    }
+
+    pub fn push_back(&mut self, element: T) {
+        self.grow_if_full();
+        let index = self.physical(self.len);
+        self.buffer[index] = MaybeUninit::new(element);
+        self.len += 1;
+    }
+
+    pub fn pop_front(&mut self) -> Option<T> {
+        if self.len == 0 {
+            return None;
+        }
+        // SAFETY: the head slot is initialized and is marked free right after the read
+        let element = unsafe { self.buffer[self.head].assume_init_read() };
+        self.head = self.physical(1);
+        self.len -= 1;
+        Some(element)
+    }
+
+    pub fn pop_back(&mut self) -> Option<T> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        let index = self.physical(self.len);
+        // SAFETY: the last slot is initialized and was just marked free
+        Some(unsafe { self.buffer[index].assume_init_read() })
+    }
+
+    pub fn front(&self) -> Option<&T> {
+        self.get(0)
+    }
+
+    pub fn front_mut(&mut self) -> Option<&mut T> {
+        self.get_mut(0)
+    }
+
+    pub fn back(&self) -> Option<&T> {
+        self.get(self.len.checked_sub(1)?)
+    }
+
+    pub fn back_mut(&mut self) -> Option<&mut T> {
+        self.get_mut(self.len.checked_sub(1)?)
+    }
+
+    /// Returns the element `index` positions from the front.
+    pub fn get(&self, index: usize) -> Option<&T> {
+        if index < self.len {
+            let index = self.physical(index);
+            // SAFETY: slots of the first `len` positions are initialized
+            Some(unsafe { self.buffer[index].assume_init_ref() })
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        if index < self.len {
+            let index = self.physical(index);
+            // SAFETY: slots of the first `len` positions are initialized
+            Some(unsafe { self.buffer[index].assume_init_mut() })
+        } else {
+            None
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn clear(&mut self) {
+        while self.pop_back().is_some() {}
+        self.head = 0;
+    }
+
+    /// Rotates the elements `n` places to the left, so the element at `n` becomes the
+    /// front. Panics if `n` is greater than `len`.
+    pub fn rotate_left(&mut self, n: usize) {
+        assert!(
+            n <= self.len,
+            "Cannot rotate a Deque of {} by {}",
+            self.len,
+            n
+        );
+        if n > self.len - n {
+            return self.rotate_right(self.len - n);
+        }
+        for _ in 0..n {
+            // Moving an element between the ends never needs to grow the buffer
+            let element = self.pop_front().unwrap();
+            self.push_back(element);
+        }
+    }
+
+    /// Rotates the elements `n` places to the right, so the last `n` elements move to
+    /// the front. Panics if `n` is greater than `len`.
+    pub fn rotate_right(&mut self, n: usize) {
+        assert!(
+            n <= self.len,
+            "Cannot rotate a Deque of {} by {}",
+            self.len,
+            n
+        );
+        if n > self.len - n {
+            return self.rotate_left(self.len - n);
+        }
+        for _ in 0..n {
+            let element = self.pop_back().unwrap();
+            self.push_front(element);
+        }
+    }
+
+    /// Rearranges the buffer so that the elements are stored in order in a single slice.
+    pub fn make_contiguous(&mut self) -> &mut [T] {
+        if self.head + self.len > self.buffer.len() {
+            self.buffer.rotate_left(self.head);
+            self.head = 0;
+        }
+        self.as_mut_slices().0
+    }
+
+    /// Returns the elements as two slices that together hold them in order. The second
+    /// one is only non-empty when the elements wrap around the end of the buffer.
+    pub fn as_slices(&self) -> (&[T], &[T]) {
+        let (front, back) = self.ranges();
+        // SAFETY: both ranges only cover initialized slots
+        unsafe {
+            (
+                slice_assume_init(&self.buffer[front.0..front.1]),
+                slice_assume_init(&self.buffer[back.0..back.1]),
+            )
+        }
+    }
+
+    pub fn as_mut_slices(&mut self) -> (&mut [T], &mut [T]) {
+        let (front, back) = self.ranges();
+        // The back range always lies before the front one in the buffer
+        let (start, end) = self.buffer.split_at_mut(front.0);
+        // SAFETY: both ranges only cover initialized slots
+        unsafe {
+            (
+                slice_assume_init_mut(&mut end[..front.1 - front.0]),
+                slice_assume_init_mut(&mut start[back.0..back.1]),
+            )
+        }
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        let (front, back) = self.as_slices();
+        Iter {
+            front: front.iter(),
+            back: back.iter(),
+        }
+    }
+
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        let (front, back) = self.as_mut_slices();
+        IterMut {
+            front: front.iter_mut(),
+            back: back.iter_mut(),
+        }
+    }
+
+    fn ranges(&self) -> ((usize, usize), (usize, usize)) {
+        let capacity = self.buffer.len();
+        if self.head + self.len <= capacity {
+            ((self.head, self.head + self.len), (0, 0))
+        } else {
+            ((self.head, capacity), (0, self.head + self.len - capacity))
+        }
+    }
+
+    fn physical(&self, index: usize) -> usize {
+        let index = self.head + index;
+        if index >= self.buffer.len() {
+            index - self.buffer.len()
+        } else {
+            index
+        }
+    }
+
+    fn wrap_sub(&self, index: usize, n: usize) -> usize {
+        if index >= n {
+            index - n
+        } else {
+            index + self.buffer.len() - n
+        }
+    }
+
+    fn grow_if_full(&mut self) {
+        if self.len < self.buffer.len() {
+            return;
+        }
+        let capacity = usize::max(MIN_CAPACITY, self.buffer.len() * 2);
+        let mut buffer = Self::uninit_buffer(capacity);
+        for (index, slot) in buffer.iter_mut().take(self.len).enumerate() {
+            let old_index = self.physical(index);
+            // SAFETY: every element is read exactly once and the old buffer is dropped
+            // without dropping its contents, since `MaybeUninit` never does
+            *slot = MaybeUninit::new(unsafe { self.buffer[old_index].assume_init_read() });
+        }
+        self.buffer = buffer;
+        self.head = 0;
+    }
+
+    fn uninit_buffer(capacity: usize) -> Vec<MaybeUninit<T>> {
+        (0..capacity).map(|_| MaybeUninit::uninit()).collect()
+    }
+}
+
+// SAFETY: callers guarantee that every slot of the slice is initialized
+unsafe fn slice_assume_init<T>(slice: &[MaybeUninit<T>]) -> &[T] {
+    &*(slice as *const [MaybeUninit<T>] as *const [T])
+}
+
+// SAFETY: callers guarantee that every slot of the slice is initialized
+unsafe fn slice_assume_init_mut<T>(slice: &mut [MaybeUninit<T>]) -> &mut [T] {
+    &mut *(slice as *mut [MaybeUninit<T>] as *mut [T])
+}
+
+impl<T> VectorBasedDataStructure<T> for Deque<T> {
+    fn new() -> Self {
+        Self::with_capacity(0)
+    }
+
+    fn with_capacity(capacity: usize) -> Self {
+        Deque {
+            buffer: Self::uninit_buffer(capacity),
+            head: 0,
+            len: 0,
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    fn capacity(&self) -> usize {
+        self.buffer.len()
+    }
+}
+
+impl<T> QueueLike<T> for Deque<T> {
+    fn enqueue(&mut self, element: T) {
+        self.push_back(element);
+    }
+
+    fn dequeue(&mut self) -> Option<T> {
+        self.pop_front()
+    }
+
+    fn peek(&self) -> Option<&T> {
+        self.front()
+    }
+
+    fn peek_back(&self) -> Option<&T> {
+        self.back()
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn clear(&mut self) {
+        self.clear();
+    }
+}
+
+impl<T> Drop for Deque<T> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+impl<T> Default for Deque<T> {
+    fn default() -> Self {
+        VectorBasedDataStructure::new()
+    }
+}
+
+impl<T> Index<usize> for Deque<T> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        self.get(index)
+            .expect("Index is out of bounds of the Deque")
+    }
+}
+
+impl<T> IndexMut<usize> for Deque<T> {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        self.get_mut(index)
+            .expect("Index is out of bounds of the Deque")
+    }
+}
+
+impl<T: Clone> Clone for Deque<T> {
+    fn clone(&self) -> Self {
+        self.iter().cloned().collect()
+    }
+}
+
+impl<T: Debug> Debug for Deque<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: PartialEq> PartialEq for Deque<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Eq> Eq for Deque<T> {}
+
+impl<T: PartialOrd> PartialOrd for Deque<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.iter().partial_cmp(other.iter())
+    }
+}
+
+impl<T: Ord> Ord for Deque<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.iter().cmp(other.iter())
+    }
+}
+
+impl<T: Hash> Hash for Deque<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.len.hash(state);
+        for element in self.iter() {
+            element.hash(state);
+        }
+    }
+}
+
+impl<T> From<Vec<T>> for Deque<T> {
+    fn from(vec: Vec<T>) -> Self {
+        vec.into_iter().collect()
+    }
+}
+
+impl<T> FromIterator<T> for Deque<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let iter = iter.into_iter();
+        let mut deque = Deque::with_capacity(iter.size_hint().0);
+        deque.extend(iter);
+        deque
+    }
+}
+
+impl<T> Extend<T> for Deque<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for element in iter {
+            self.push_back(element);
+        }
+    }
+}
+
+impl<T> IntoIterator for Deque<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter(self)
+    }
+}
+
+impl<'a, T> IntoIterator for &'a Deque<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut Deque<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+pub struct IntoIter<T>(Deque<T>);
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.pop_front()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.0.len, Some(self.0.len))
+    }
+}
+
+impl<T> DoubleEndedIterator for IntoIter<T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.pop_back()
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+pub struct Iter<'a, T> {
+    front: slice::Iter<'a, T>,
+    back: slice::Iter<'a, T>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.front.next().or_else(|| self.back.next())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.front.len() + self.back.len();
+        (len, Some(len))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for Iter<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.back.next_back().or_else(|| self.front.next_back())
+    }
+}
+
+impl<'a, T> ExactSizeIterator for Iter<'a, T> {}
+
+pub struct IterMut<'a, T> {
+    front: slice::IterMut<'a, T>,
+    back: slice::IterMut<'a, T>,
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.front.next().or_else(|| self.back.next())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.front.len() + self.back.len();
+        (len, Some(len))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for IterMut<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.back.next_back().or_else(|| self.front.next_back())
+    }
+}
+
+impl<'a, T> ExactSizeIterator for IterMut<'a, T> {}
+
+#[cfg(test)]
+mod tests {
+    use crate::deque::Deque;
+    use crate::queue_like::queue_like_tests;
+    use crate::vector_based::VectorBasedDataStructure;
+
+    queue_like_tests!(queue_like, Deque<i32>);
+
+    // Builds a deque whose elements wrap around the end of its buffer
+    fn wrapped() -> Deque<i32> {
+        let mut deque = Deque::with_capacity(4);
+        deque.push_back(2);
+        deque.push_back(3);
+        deque.push_front(1);
+        deque.push_front(0);
+        assert_eq!(deque.capacity(), 4);
+        deque
+    }
+
+    #[test]
+    fn new_and_with_capacity() {
+        let deque: Deque<i32> = Deque::new();
+        assert_eq!(deque.len(), 0);
+        assert_eq!(deque.capacity(), 0);
+        let deque: Deque<i32> = Deque::with_capacity(10);
+        assert!(deque.is_empty());
+        assert_eq!(deque.capacity(), 10);
+    }
+
+    #[test]
+    fn push_and_pop_both_ends() {
+        let mut deque = Deque::new();
+        deque.push_back(2);
+        deque.push_front(1);
+        deque.push_back(3);
+        deque.push_front(0);
+        assert_eq!(deque.len(), 4);
+        assert_eq!(deque.front(), Some(&0));
+        assert_eq!(deque.back(), Some(&3));
+        assert_eq!(deque.pop_back(), Some(3));
+        assert_eq!(deque.pop_front(), Some(0));
+        assert_eq!(deque.pop_front(), Some(1));
+        assert_eq!(deque.pop_back(), Some(2));
+        assert_eq!(deque.pop_back(), None);
+        assert_eq!(deque.pop_front(), None);
+        assert_eq!(deque.back(), None);
+    }
+
+    #[test]
+    fn grows_while_wrapped() {
+        let mut deque = wrapped();
+        deque.push_front(-1);
+        deque.push_back(4);
+        assert_eq!(deque.capacity(), 8);
+        assert_eq!(
+            deque.iter().copied().collect::<Vec<_>>(),
+            vec![-1, 0, 1, 2, 3, 4]
+        );
+        for i in 5..100 {
+            deque.push_back(i);
+        }
+        assert_eq!(deque.len(), 101);
+        assert!(deque.iter().copied().eq(-1..100));
+    }
+
+    #[test]
+    fn index() {
+        let mut deque = wrapped();
+        assert_eq!(deque[0], 0);
+        assert_eq!(deque[3], 3);
+        deque[1] = 10;
+        *deque.get_mut(2).unwrap() += 10;
+        assert_eq!(deque.get(1), Some(&10));
+        assert_eq!(deque.get(2), Some(&12));
+        assert_eq!(deque.get(4), None);
+        *deque.front_mut().unwrap() = -1;
+        *deque.back_mut().unwrap() = -2;
+        assert_eq!(deque, Deque::from(vec![-1, 10, 12, -2]));
+    }
+
+    #[test]
+    #[should_panic(expected = "Index is out of bounds of the Deque")]
+    fn index_out_of_bounds() {
+        let deque = wrapped();
+        let _ = deque[4];
+    }
+
+    #[test]
+    fn rotate() {
+        let mut deque: Deque<i32> = (0..5).collect();
+        deque.rotate_left(2);
+        assert_eq!(deque, Deque::from(vec![2, 3, 4, 0, 1]));
+        deque.rotate_right(2);
+        assert_eq!(deque, Deque::from(vec![0, 1, 2, 3, 4]));
+        deque.rotate_left(4);
+        assert_eq!(deque, Deque::from(vec![4, 0, 1, 2, 3]));
+        deque.rotate_right(5);
+        deque.rotate_left(0);
+        assert_eq!(deque, Deque::from(vec![4, 0, 1, 2, 3]));
+        let mut full = wrapped();
+        full.rotate_right(1);
+        assert_eq!(full, Deque::from(vec![3, 0, 1, 2]));
+        assert_eq!(full.capacity(), 4);
+    }
+
+    #[test]
+    #[should_panic(expected = "Cannot rotate a Deque of 2 by 3")]
+    fn rotate_too_far() {
+        Deque::from(vec![1, 2]).rotate_left(3);
+    }
+
+    #[test]
+    fn slices_and_make_contiguous() {
+        let mut deque = wrapped();
+        assert_eq!(deque.as_slices(), (&[0, 1][..], &[2, 3][..]));
+        {
+            let (front, back) = deque.as_mut_slices();
+            front[0] = 10;
+            back[1] = 13;
+        }
+        assert_eq!(deque.make_contiguous(), &mut [10, 1, 2, 13]);
+        assert_eq!(deque.as_slices(), (&[10, 1, 2, 13][..], &[][..]));
+        deque.make_contiguous().sort_unstable_by(|a, b| b.cmp(a));
+        assert_eq!(deque, Deque::from(vec![13, 10, 2, 1]));
+        deque.push_back(0);
+        assert_eq!(deque.pop_front(), Some(13));
+        let empty: Deque<i32> = Deque::new();
+        assert_eq!(empty.as_slices(), (&[][..], &[][..]));
+    }
+
+    #[test]
+    fn double_ended_iterators() {
+        let mut deque = wrapped();
+        let mut iter = deque.iter();
+        assert_eq!(iter.len(), 4);
+        assert_eq!(iter.next(), Some(&0));
+        assert_eq!(iter.next_back(), Some(&3));
+        assert_eq!(iter.next_back(), Some(&2));
+        assert_eq!(iter.next_back(), Some(&1));
+        assert_eq!(iter.next(), None);
+        for element in deque.iter_mut().rev().take(2) {
+            *element *= 10;
+        }
+        for element in &mut deque {
+            *element += 1;
+        }
+        assert_eq!(
+            (&deque).into_iter().copied().collect::<Vec<_>>(),
+            vec![1, 2, 21, 31]
+        );
+        let mut into_iter = deque.into_iter();
+        assert_eq!(into_iter.next_back(), Some(31));
+        assert_eq!(into_iter.next(), Some(1));
+        assert_eq!(into_iter.len(), 2);
+    }
+
+    #[test]
+    fn drops_every_element() {
+        use std::rc::Rc;
+
+        let counter = Rc::new(());
+        let mut deque = Deque::with_capacity(4);
+        for _ in 0..3 {
+            deque.push_back(Rc::clone(&counter));
+        }
+        deque.push_front(Rc::clone(&counter));
+        deque.push_front(Rc::clone(&counter));
+        deque.pop_back();
+        assert_eq!(Rc::strong_count(&counter), 5);
+        let mut iter = deque.clone().into_iter();
+        iter.next();
+        drop(iter);
+        assert_eq!(Rc::strong_count(&counter), 5);
+        drop(deque);
+        assert_eq!(Rc::strong_count(&counter), 1);
+    }
+
+    #[test]
+    fn std_traits() {
+        use std::collections::HashSet;
+
+        let deque = wrapped();
+        let copy = deque.clone();
+        assert_eq!(deque, copy);
+        assert_eq!(format!("{:?}", deque), "[0, 1, 2, 3]");
+        assert_eq!(Deque::<i32>::default(), Deque::new());
+        assert!(deque < Deque::from(vec![0, 2]));
+        let set: HashSet<Deque<i32>> = vec![deque, copy, Deque::new()].into_iter().collect();
+        assert_eq!(set.len(), 2);
+    }
+
+    #[test]
+    fn sliding_window_maximum() {
+        // Indices of candidates for the maximum, with decreasing values from the front
+        let values = [1, 3, -1, -3, 5, 3, 6, 7];
+        let window = 3;
+        let mut candidates: Deque<usize> = Deque::new();
+        let mut maximums = Vec::new();
+        for (index, value) in values.iter().enumerate() {
+            while candidates
+                .back()
+                .is_some_and(|&last| values[last] <= *value)
+            {
+                candidates.pop_back();
+            }
+            candidates.push_back(index);
+            if candidates[0] + window <= index {
+                candidates.pop_front();
+            }
+            if index + 1 >= window {
+                maximums.push(values[candidates[0]]);
+            }
+        }
+        assert_eq!(maximums, vec![3, 3, 5, 5, 6, 7]);
+    }
+}
@@ -0,0 +1,580 @@
+use crate::queue_like::QueueLike;
+use std::cmp::Ordering;
+use std::fmt::{self, Debug};
+use std::hash::{Hash, Hasher};
+use std::iter::FromIterator;
+use std::marker::PhantomData;
+use std::ptr::NonNull;
+
+/// Doubly linked list with O(1) operations at both ends and O(1) `append`.
+///
+/// Nodes are heap allocated and linked through raw pointers. Every unsafe block relies on
+/// the same invariant: `head`, `tail` and the `prev`/`next` links only ever point to live
+/// nodes owned by this list, and `len` matches the number of those nodes. The tests are
+/// run under Miri with `cargo +nightly miri test doubly_linked_list`.
+pub struct DoublyLinkedList<T> {
+    head: Option<NonNull<Node<T>>>,
+    tail: Option<NonNull<Node<T>>>,
+    len: usize,
+    marker: PhantomData<Box<Node<T>>>,
+}
+
+struct Node<T> {
+    data: T,
+    prev: Option<NonNull<Node<T>>>,
+    next: Option<NonNull<Node<T>>>,
+}
+
+// SAFETY: the list uniquely owns its nodes, as a `Box<Node<T>>` chain would, so sharing
+// or sending it is as safe as sharing or sending the `T`s themselves
+unsafe impl<T: Send> Send for DoublyLinkedList<T> {}
+unsafe impl<T: Sync> Sync for DoublyLinkedList<T> {}
+
+impl<T> Default for DoublyLinkedList<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> DoublyLinkedList<T> {
+    pub fn new() -> Self {
+        DoublyLinkedList {
+            head: None,
+            tail: None,
+            len: 0,
+            marker: PhantomData,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn push_front(&mut self, value: T) {
+        let node = Self::allocate(value, None, self.head);
+        match self.head {
+            // SAFETY: `head` points to a live node owned by this list
+            Some(head) => unsafe { (*head.as_ptr()).prev = Some(node) },
+            None => self.tail = Some(node),
+        }
+        self.head = Some(node);
+        self.len += 1;
+    }
+
+    pub fn push_back(&mut self, value: T) {
+        let node = Self::allocate(value, self.tail, None);
+        match self.tail {
+            // SAFETY: `tail` points to a live node owned by this list
+            Some(tail) => unsafe { (*tail.as_ptr()).next = Some(node) },
+            None => self.head = Some(node),
+        }
+        self.tail = Some(node);
+        self.len += 1;
+    }
+
+    pub fn pop_front(&mut self) -> Option<T> {
+        self.head.map(|head| {
+            // SAFETY: `head` was leaked from a `Box` in `allocate` and only this list owns
+            // it. Nothing points to it once `head` moves on and the next node's `prev` is
+            // cleared below
+            let node = unsafe { Box::from_raw(head.as_ptr()) };
+            self.head = node.next;
+            match self.head {
+                // SAFETY: a `next` link always points to a live node of this list
+                Some(new_head) => unsafe { (*new_head.as_ptr()).prev = None },
+                None => self.tail = None,
+            }
+            self.len -= 1;
+            node.data
+        })
+    }
+
+    pub fn pop_back(&mut self) -> Option<T> {
+        self.tail.map(|tail| {
+            // SAFETY: `tail` was leaked from a `Box` in `allocate` and only this list owns
+            // it. Nothing points to it once `tail` moves back and the previous node's `next`
+            // is cleared below
+            let node = unsafe { Box::from_raw(tail.as_ptr()) };
+            self.tail = node.prev;
+            match self.tail {
+                // SAFETY: a `prev` link always points to a live node of this list
+                Some(new_tail) => unsafe { (*new_tail.as_ptr()).next = None },
+                None => self.head = None,
+            }
+            self.len -= 1;
+            node.data
+        })
+    }
+
+    pub fn front(&self) -> Option<&T> {
+        // SAFETY: the node lives as long as the list is borrowed
+        self.head.map(|head| unsafe { &(*head.as_ptr()).data })
+    }
+
+    pub fn front_mut(&mut self) -> Option<&mut T> {
+        // SAFETY: the node lives as long as the list is mutably borrowed
+        self.head.map(|head| unsafe { &mut (*head.as_ptr()).data })
+    }
+
+    pub fn back(&self) -> Option<&T> {
+        // SAFETY: the node lives as long as the list is borrowed
+        self.tail.map(|tail| unsafe { &(*tail.as_ptr()).data })
+    }
+
+    pub fn back_mut(&mut self) -> Option<&mut T> {
+        // SAFETY: the node lives as long as the list is mutably borrowed
+        self.tail.map(|tail| unsafe { &mut (*tail.as_ptr()).data })
+    }
+
+    /// Moves every element of `other` to the back of `self`, leaving `other` empty.
+    pub fn append(&mut self, other: &mut DoublyLinkedList<T>) {
+        let (other_head, other_tail) = match (other.head.take(), other.tail.take()) {
+            (Some(head), Some(tail)) => (head, tail),
+            _ => return,
+        };
+        match self.tail {
+            // SAFETY: both nodes are live and `other` no longer refers to its nodes
+            Some(tail) => unsafe {
+                (*tail.as_ptr()).next = Some(other_head);
+                (*other_head.as_ptr()).prev = Some(tail);
+            },
+            None => self.head = Some(other_head),
+        }
+        self.tail = Some(other_tail);
+        self.len += std::mem::replace(&mut other.len, 0);
+    }
+
+    pub fn clear(&mut self) {
+        while self.pop_front().is_some() {}
+    }
+
+    pub fn contains(&self, value: &T) -> bool
+    where
+        T: PartialEq,
+    {
+        self.iter().any(|element| element == value)
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            head: self.head,
+            tail: self.tail,
+            len: self.len,
+            marker: PhantomData,
+        }
+    }
+
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut {
+            head: self.head,
+            tail: self.tail,
+            len: self.len,
+            marker: PhantomData,
+        }
+    }
+
+    fn allocate(
+        value: T,
+        prev: Option<NonNull<Node<T>>>,
+        next: Option<NonNull<Node<T>>>,
+    ) -> NonNull<Node<T>> {
+        let node = Box::new(Node {
+            data: value,
+            prev,
+            next,
+        });
+        NonNull::from(Box::leak(node))
+    }
+}
+
+impl<T> QueueLike<T> for DoublyLinkedList<T> {
+    fn enqueue(&mut self, element: T) {
+        self.push_back(element);
+    }
+
+    fn dequeue(&mut self) -> Option<T> {
+        self.pop_front()
+    }
+
+    fn peek(&self) -> Option<&T> {
+        self.front()
+    }
+
+    fn peek_back(&self) -> Option<&T> {
+        self.back()
+    }
+
+    fn len(&self) -> usize {
+        self.len()
+    }
+
+    fn clear(&mut self) {
+        self.clear();
+    }
+}
+
+impl<T> Drop for DoublyLinkedList<T> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+impl<T: Clone> Clone for DoublyLinkedList<T> {
+    fn clone(&self) -> Self {
+        self.iter().cloned().collect()
+    }
+}
+
+impl<T: Debug> Debug for DoublyLinkedList<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: PartialEq> PartialEq for DoublyLinkedList<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Eq> Eq for DoublyLinkedList<T> {}
+
+impl<T: PartialOrd> PartialOrd for DoublyLinkedList<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.iter().partial_cmp(other.iter())
+    }
+}
+
+impl<T: Ord> Ord for DoublyLinkedList<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.iter().cmp(other.iter())
+    }
+}
+
+impl<T: Hash> Hash for DoublyLinkedList<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.len.hash(state);
+        for element in self.iter() {
+            element.hash(state);
+        }
+    }
+}
+
+impl<T> From<Vec<T>> for DoublyLinkedList<T> {
+    fn from(vec: Vec<T>) -> Self {
+        vec.into_iter().collect()
+    }
+}
+
+impl<T> FromIterator<T> for DoublyLinkedList<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut list = DoublyLinkedList::new();
+        list.extend(iter);
+        list
+    }
+}
+
+impl<T> Extend<T> for DoublyLinkedList<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for element in iter {
+            self.push_back(element);
+        }
+    }
+}
+
+impl<T> IntoIterator for DoublyLinkedList<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter(self)
+    }
+}
+
+impl<'a, T> IntoIterator for &'a DoublyLinkedList<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut DoublyLinkedList<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+pub struct IntoIter<T>(DoublyLinkedList<T>);
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.pop_front()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.0.len, Some(self.0.len))
+    }
+}
+
+impl<T> DoubleEndedIterator for IntoIter<T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.pop_back()
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+// Both iterators walk inwards from the two ends and stop once `len` elements were
+// yielded, so the front and back never hand out the same node twice.
+pub struct Iter<'a, T> {
+    head: Option<NonNull<Node<T>>>,
+    tail: Option<NonNull<Node<T>>>,
+    len: usize,
+    marker: PhantomData<&'a Node<T>>,
+}
+
+// SAFETY: `Iter` only hands out `&T`, so it behaves like a `&DoublyLinkedList<T>`
+unsafe impl<T: Sync> Send for Iter<'_, T> {}
+unsafe impl<T: Sync> Sync for Iter<'_, T> {}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.head.map(|head| {
+            self.len -= 1;
+            // SAFETY: the list is borrowed for 'a, so the node stays alive
+            let node = unsafe { &*head.as_ptr() };
+            self.head = node.next;
+            &node.data
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for Iter<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.tail.map(|tail| {
+            self.len -= 1;
+            // SAFETY: the list is borrowed for 'a, so the node stays alive
+            let node = unsafe { &*tail.as_ptr() };
+            self.tail = node.prev;
+            &node.data
+        })
+    }
+}
+
+impl<'a, T> ExactSizeIterator for Iter<'a, T> {}
+
+pub struct IterMut<'a, T> {
+    head: Option<NonNull<Node<T>>>,
+    tail: Option<NonNull<Node<T>>>,
+    len: usize,
+    marker: PhantomData<&'a mut Node<T>>,
+}
+
+// SAFETY: `IterMut` hands out `&mut T` for nodes nothing else can reach while it lives,
+// so it behaves like a `&mut DoublyLinkedList<T>`
+unsafe impl<T: Send> Send for IterMut<'_, T> {}
+unsafe impl<T: Sync> Sync for IterMut<'_, T> {}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.head.map(|head| {
+            self.len -= 1;
+            // SAFETY: the list is mutably borrowed for 'a and every node is yielded once
+            let node = unsafe { &mut *head.as_ptr() };
+            self.head = node.next;
+            &mut node.data
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for IterMut<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.tail.map(|tail| {
+            self.len -= 1;
+            // SAFETY: the list is mutably borrowed for 'a and every node is yielded once
+            let node = unsafe { &mut *tail.as_ptr() };
+            self.tail = node.prev;
+            &mut node.data
+        })
+    }
+}
+
+impl<'a, T> ExactSizeIterator for IterMut<'a, T> {}
+
+#[cfg(test)]
+mod tests {
+    use crate::doubly_linked_list::{DoublyLinkedList, Iter, IterMut};
+    use crate::queue_like::queue_like_tests;
+
+    queue_like_tests!(queue_like, DoublyLinkedList<i32>);
+
+    fn to_vec<T: Clone>(list: &DoublyLinkedList<T>) -> Vec<T> {
+        list.iter().cloned().collect()
+    }
+
+    #[test]
+    fn push_front_and_back() {
+        let mut list = DoublyLinkedList::new();
+        assert!(list.is_empty());
+        list.push_back(2);
+        list.push_back(3);
+        list.push_front(1);
+        list.push_front(0);
+        assert_eq!(list.len(), 4);
+        assert_eq!(to_vec(&list), vec![0, 1, 2, 3]);
+        assert_eq!(list.front(), Some(&0));
+        assert_eq!(list.back(), Some(&3));
+    }
+
+    #[test]
+    fn pop_front_and_back() {
+        let mut list = DoublyLinkedList::from(vec![1, 2, 3, 4]);
+        assert_eq!(list.pop_front(), Some(1));
+        assert_eq!(list.pop_back(), Some(4));
+        assert_eq!(list.pop_back(), Some(3));
+        assert_eq!(list.front(), Some(&2));
+        assert_eq!(list.back(), Some(&2));
+        assert_eq!(list.pop_front(), Some(2));
+        assert_eq!(list.pop_front(), None);
+        assert_eq!(list.pop_back(), None);
+        assert_eq!(list.front(), None);
+        assert_eq!(list.back(), None);
+        list.push_back(5);
+        assert_eq!(list.front(), Some(&5));
+        assert_eq!(list.back(), Some(&5));
+    }
+
+    #[test]
+    fn front_and_back_mut() {
+        let mut list = DoublyLinkedList::from(vec![1, 2, 3]);
+        *list.front_mut().unwrap() *= 10;
+        *list.back_mut().unwrap() *= 100;
+        assert_eq!(to_vec(&list), vec![10, 2, 300]);
+    }
+
+    #[test]
+    fn append() {
+        let mut first = DoublyLinkedList::from(vec![1, 2]);
+        let mut second = DoublyLinkedList::from(vec![3, 4, 5]);
+        first.append(&mut second);
+        assert_eq!(first.len(), 5);
+        assert!(second.is_empty());
+        assert_eq!(second.front(), None);
+        assert_eq!(to_vec(&first), vec![1, 2, 3, 4, 5]);
+        assert_eq!(first.pop_back(), Some(5));
+        let mut empty = DoublyLinkedList::new();
+        empty.append(&mut first);
+        assert_eq!(to_vec(&empty), vec![1, 2, 3, 4]);
+        empty.append(&mut first);
+        assert_eq!(empty.len(), 4);
+        second.push_back(9);
+        assert_eq!(to_vec(&second), vec![9]);
+    }
+
+    #[test]
+    fn double_ended_iter() {
+        let list = DoublyLinkedList::from(vec![1, 2, 3, 4, 5]);
+        let mut iter = list.iter();
+        assert_eq!(iter.len(), 5);
+        assert_eq!(iter.next(), Some(&1));
+        assert_eq!(iter.next_back(), Some(&5));
+        assert_eq!(iter.next(), Some(&2));
+        assert_eq!(iter.next_back(), Some(&4));
+        assert_eq!(iter.next(), Some(&3));
+        assert_eq!(iter.next_back(), None);
+        assert_eq!(iter.next(), None);
+        assert_eq!(
+            list.iter().rev().cloned().collect::<Vec<_>>(),
+            vec![5, 4, 3, 2, 1]
+        );
+    }
+
+    #[test]
+    fn iter_mut() {
+        let mut list = DoublyLinkedList::from(vec![1, 2, 3, 4]);
+        for element in list.iter_mut().rev().take(2) {
+            *element *= 10;
+        }
+        for element in &mut list {
+            *element += 1;
+        }
+        assert_eq!(to_vec(&list), vec![2, 3, 31, 41]);
+    }
+
+    #[test]
+    fn into_iter() {
+        let list = DoublyLinkedList::from(vec!["a".to_string(), "b".to_string(), "c".to_string()]);
+        let mut iter = list.into_iter();
+        assert_eq!(iter.next_back(), Some("c".to_string()));
+        assert_eq!(iter.next(), Some("a".to_string()));
+        assert_eq!(iter.len(), 1);
+        // The remaining element is freed when the iterator is dropped
+    }
+
+    #[test]
+    fn contains_and_clear() {
+        let mut list: DoublyLinkedList<i32> = (0..10).collect();
+        assert!(list.contains(&9));
+        assert!(!list.contains(&10));
+        list.clear();
+        assert!(list.is_empty());
+        assert!(!list.contains(&9));
+        list.extend(vec![1, 2]);
+        assert_eq!(to_vec(&list), vec![1, 2]);
+    }
+
+    #[test]
+    fn clone_eq_and_debug() {
+        let list: DoublyLinkedList<i32> = vec![1, 2, 3].into_iter().collect();
+        let mut copy = list.clone();
+        assert_eq!(list, copy);
+        copy.push_back(4);
+        assert_ne!(list, copy);
+        assert!(list < copy);
+        assert_eq!(format!("{:?}", list), "[1, 2, 3]");
+        assert_eq!(format!("{:?}", DoublyLinkedList::<i32>::new()), "[]");
+    }
+
+    #[test]
+    fn iterators_are_send_and_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<DoublyLinkedList<String>>();
+        assert_send_sync::<Iter<'_, String>>();
+        assert_send_sync::<IterMut<'_, String>>();
+    }
+}
@@ -0,0 +1,52 @@
+use std::error::Error;
+use std::fmt;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DestructError {
+    IndexOutOfBounds {
+        index: usize,
+        len: usize,
+    },
+    ElementNotFound,
+    /// A priority was moved in the opposite direction of the one requested.
+    InvalidPriority,
+}
+
+impl fmt::Display for DestructError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DestructError::IndexOutOfBounds { index, len } => write!(
+                f,
+                "index {} is out of bounds for a length of {}",
+                index, len
+            ),
+            DestructError::ElementNotFound => write!(f, "element was not found"),
+            DestructError::InvalidPriority => {
+                write!(f, "priority change goes in the wrong direction")
+            }
+        }
+    }
+}
+
+impl Error for DestructError {}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::DestructError;
+
+    #[test]
+    fn display() {
+        assert_eq!(
+            DestructError::IndexOutOfBounds { index: 5, len: 3 }.to_string(),
+            "index 5 is out of bounds for a length of 3"
+        );
+        assert_eq!(
+            DestructError::ElementNotFound.to_string(),
+            "element was not found"
+        );
+        assert_eq!(
+            DestructError::InvalidPriority.to_string(),
+            "priority change goes in the wrong direction"
+        );
+    }
+}
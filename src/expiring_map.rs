@@ -0,0 +1,329 @@
+use crate::hashmap::HashMap;
+use std::borrow::Borrow;
+use std::cell::Cell;
+use std::hash::Hash;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+pub trait Clock {
+    fn now(&self) -> Instant;
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+/// Clock that only moves when told to. Clones share the same time, so a test can keep
+/// one handle and pass another to the map.
+#[derive(Clone, Debug)]
+pub struct ManualClock {
+    now: Rc<Cell<Instant>>,
+}
+
+impl ManualClock {
+    pub fn new() -> Self {
+        ManualClock {
+            now: Rc::new(Cell::new(Instant::now())),
+        }
+    }
+
+    /// Moves the clock forward by `duration`.
+    ///
+    /// Panics if the new time is past what `Instant` can represent. Entries whose TTL
+    /// reaches that far never expire, so no clock has to get there.
+    pub fn advance(&self, duration: Duration) {
+        let now = self
+            .now
+            .get()
+            .checked_add(duration)
+            .expect("ManualClock advanced past the range of Instant");
+        self.now.set(now);
+    }
+}
+
+impl Default for ManualClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> Instant {
+        self.now.get()
+    }
+}
+
+/// Map whose entries expire after a time-to-live. Expired entries are dropped lazily
+/// when they are accessed, or all at once by `purge_expired`.
+pub struct ExpiringMap<K: Hash + PartialEq, V, C: Clock = SystemClock> {
+    // `None` marks an entry whose TTL reaches past what `Instant` can represent
+    map: HashMap<K, (V, Option<Instant>)>,
+    default_ttl: Duration,
+    clock: C,
+}
+
+impl<K: Hash + PartialEq, V> ExpiringMap<K, V> {
+    pub fn new(default_ttl: Duration) -> Self {
+        Self::with_clock(default_ttl, SystemClock)
+    }
+}
+
+impl<K: Hash + PartialEq, V, C: Clock> ExpiringMap<K, V, C> {
+    pub fn with_clock(default_ttl: Duration, clock: C) -> Self {
+        ExpiringMap {
+            map: HashMap::new(),
+            default_ttl,
+            clock,
+        }
+    }
+
+    pub fn default_ttl(&self) -> Duration {
+        self.default_ttl
+    }
+
+    /// Inserts or replaces an entry that expires after the default TTL.
+    pub fn put(&mut self, key: K, value: V) {
+        self.put_with_ttl(key, value, self.default_ttl);
+    }
+
+    /// Inserts or replaces an entry that expires after `ttl`. A TTL too large for the
+    /// clock, such as `Duration::MAX`, never expires.
+    pub fn put_with_ttl(&mut self, key: K, value: V, ttl: Duration) {
+        let expires_at = self.clock.now().checked_add(ttl);
+        self.map.put(key, (value, expires_at));
+    }
+
+    /// Returns the value if it has not expired yet, removing it otherwise.
+    pub fn get<Q>(&mut self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + PartialEq + ?Sized,
+    {
+        self.get_mut(key).map(|value| &*value)
+    }
+
+    /// Returns the value if it has not expired yet, removing it otherwise.
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + PartialEq + ?Sized,
+    {
+        self.remove_if_expired(key);
+        self.map.get_mut(key).map(|(value, _)| value)
+    }
+
+    /// Checks for a live entry without removing an expired one.
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + PartialEq + ?Sized,
+    {
+        self.time_to_live(key).is_some()
+    }
+
+    /// Time left before the entry expires, or `None` if it is missing or already expired.
+    /// An entry that never expires reports `Duration::MAX`.
+    pub fn time_to_live<Q>(&self, key: &Q) -> Option<Duration>
+    where
+        K: Borrow<Q>,
+        Q: Hash + PartialEq + ?Sized,
+    {
+        let (_, expires_at) = self.map.get(key)?;
+        let now = self.clock.now();
+        match *expires_at {
+            Some(expires_at) if expires_at > now => Some(expires_at - now),
+            Some(_) => None,
+            None => Some(Duration::MAX),
+        }
+    }
+
+    /// Removes an entry, returning its value only if it had not expired.
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + PartialEq + ?Sized,
+    {
+        let now = self.clock.now();
+        self.map
+            .remove(key)
+            .filter(|(_, expires_at)| is_live(*expires_at, now))
+            .map(|(value, _)| value)
+    }
+
+    /// Drops every expired entry and returns how many were removed.
+    pub fn purge_expired(&mut self) -> usize {
+        let now = self.clock.now();
+        let before = self.map.len();
+        self.map
+            .retain(|_, (_, expires_at)| is_live(*expires_at, now));
+        before - self.map.len()
+    }
+
+    /// Number of stored entries, including expired ones that were not purged yet.
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.map.clear();
+    }
+
+    /// Iterates over the entries that have not expired yet.
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> {
+        let now = self.clock.now();
+        self.map
+            .iter()
+            .filter(move |(_, (_, expires_at))| is_live(*expires_at, now))
+            .map(|(key, (value, _))| (key, value))
+    }
+
+    fn remove_if_expired<Q>(&mut self, key: &Q)
+    where
+        K: Borrow<Q>,
+        Q: Hash + PartialEq + ?Sized,
+    {
+        let now = self.clock.now();
+        let expired = match self.map.get(key) {
+            Some((_, expires_at)) => !is_live(*expires_at, now),
+            None => false,
+        };
+        if expired {
+            self.map.remove(key);
+        }
+    }
+}
+
+fn is_live(expires_at: Option<Instant>, now: Instant) -> bool {
+    match expires_at {
+        Some(expires_at) => expires_at > now,
+        None => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::expiring_map::{ExpiringMap, ManualClock};
+    use std::time::Duration;
+
+    fn map_with_clock() -> (ExpiringMap<&'static str, i32, ManualClock>, ManualClock) {
+        let clock = ManualClock::new();
+        let map = ExpiringMap::with_clock(Duration::from_secs(10), clock.clone());
+        (map, clock)
+    }
+
+    #[test]
+    fn expires_after_default_ttl() {
+        let (mut map, clock) = map_with_clock();
+        map.put("session", 1);
+        clock.advance(Duration::from_secs(9));
+        assert_eq!(map.get("session"), Some(&1));
+        clock.advance(Duration::from_secs(1));
+        assert_eq!(map.len(), 1);
+        assert_eq!(map.get("session"), None);
+        // The expired entry was removed on access
+        assert_eq!(map.len(), 0);
+    }
+
+    #[test]
+    fn put_with_ttl() {
+        let (mut map, clock) = map_with_clock();
+        map.put_with_ttl("short", 1, Duration::from_secs(1));
+        map.put_with_ttl("long", 2, Duration::from_secs(100));
+        assert_eq!(map.time_to_live("short"), Some(Duration::from_secs(1)));
+        clock.advance(Duration::from_secs(2));
+        assert!(!map.contains_key("short"));
+        assert!(map.contains_key("long"));
+        assert_eq!(map.time_to_live("long"), Some(Duration::from_secs(98)));
+        assert_eq!(map.time_to_live("short"), None);
+    }
+
+    #[test]
+    fn huge_ttl_never_expires() {
+        let (mut map, clock) = map_with_clock();
+        map.put_with_ttl("forever", 1, Duration::MAX);
+        assert_eq!(map.time_to_live("forever"), Some(Duration::MAX));
+        clock.advance(Duration::from_secs(1_000_000_000));
+        assert_eq!(map.get("forever"), Some(&1));
+        assert_eq!(map.purge_expired(), 0);
+        assert_eq!(map.iter().count(), 1);
+        assert_eq!(map.remove("forever"), Some(1));
+    }
+
+    #[test]
+    #[should_panic(expected = "ManualClock advanced past the range of Instant")]
+    fn clock_overflow() {
+        ManualClock::new().advance(Duration::MAX);
+    }
+
+    #[test]
+    fn put_refreshes_ttl() {
+        let (mut map, clock) = map_with_clock();
+        map.put("token", 1);
+        clock.advance(Duration::from_secs(8));
+        map.put("token", 2);
+        clock.advance(Duration::from_secs(8));
+        assert_eq!(map.get("token"), Some(&2));
+    }
+
+    #[test]
+    fn get_mut() {
+        let (mut map, clock) = map_with_clock();
+        map.put("counter", 1);
+        *map.get_mut("counter").unwrap() += 1;
+        assert_eq!(map.get("counter"), Some(&2));
+        clock.advance(Duration::from_secs(10));
+        assert_eq!(map.get_mut("counter"), None);
+    }
+
+    #[test]
+    fn remove() {
+        let (mut map, clock) = map_with_clock();
+        map.put("a", 1);
+        map.put("b", 2);
+        assert_eq!(map.remove("a"), Some(1));
+        clock.advance(Duration::from_secs(20));
+        assert_eq!(map.remove("b"), None);
+        assert!(map.is_empty());
+    }
+
+    #[test]
+    fn purge_expired() {
+        let (mut map, clock) = map_with_clock();
+        for (i, key) in ["a", "b", "c", "d"].iter().enumerate() {
+            map.put_with_ttl(key, i as i32, Duration::from_secs(i as u64 + 1));
+        }
+        clock.advance(Duration::from_secs(2));
+        assert_eq!(map.len(), 4);
+        assert_eq!(map.iter().count(), 2);
+        assert_eq!(map.purge_expired(), 2);
+        assert_eq!(map.len(), 2);
+        assert_eq!(map.purge_expired(), 0);
+        let mut keys: Vec<&str> = map.iter().map(|(key, _)| *key).collect();
+        keys.sort();
+        assert_eq!(keys, vec!["c", "d"]);
+        clock.advance(Duration::from_secs(10));
+        assert_eq!(map.purge_expired(), 2);
+        assert!(map.is_empty());
+    }
+
+    #[test]
+    fn system_clock() {
+        let mut map = ExpiringMap::new(Duration::from_secs(60));
+        map.put(1, "one");
+        assert_eq!(map.default_ttl(), Duration::from_secs(60));
+        assert_eq!(map.get(&1), Some(&"one"));
+        map.put_with_ttl(2, "two", Duration::from_secs(0));
+        assert_eq!(map.get(&2), None);
+        map.clear();
+        assert!(map.is_empty());
+    }
+}
@@ -0,0 +1,980 @@
+use std::borrow::Borrow;
+use std::collections::hash_map::RandomState;
+use std::fmt::{self, Debug};
+use std::hash::{BuildHasher, Hash};
+use std::iter::FromIterator;
+use std::marker::PhantomData;
+use std::ops::Index;
+use std::{slice, vec};
+
+const DEFAULT_SLOT_COUNT: usize = 32;
+const DEFAULT_LOAD_FACTOR: f64 = 0.75;
+
+/// Open-addressing hash map with the same API as `hashmap::HashMap`.
+///
+/// Entries live in a single flat vector and collisions are resolved with Robin Hood
+/// probing: an entry that is further from its ideal slot takes the place of a closer one.
+/// Removal shifts the following entries back instead of leaving tombstones.
+#[derive(Clone)]
+pub struct FlatHashMap<K: Hash + PartialEq, V, S = RandomState> {
+    slots: Vec<Option<Slot<K, V>>>,
+    size: usize,
+    load_factor: f64,
+    hash_builder: S,
+}
+
+#[derive(Clone)]
+struct Slot<K, V> {
+    hash: u64,
+    key: K,
+    value: V,
+}
+
+impl<K: Hash + PartialEq, V, S: BuildHasher + Default> Default for FlatHashMap<K, V, S> {
+    fn default() -> Self {
+        Self::with_hasher(S::default())
+    }
+}
+
+impl<K: Hash + PartialEq, V> FlatHashMap<K, V> {
+    pub fn new() -> Self {
+        Self::with_hasher(RandomState::new())
+    }
+
+    /// Creates a map able to hold at least `capacity` entries before it has to grow.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self::with_capacity_and_hasher(capacity, RandomState::new())
+    }
+
+    /// Creates a map able to hold at least `capacity` entries which grows once
+    /// the ratio of entries to slots exceeds `load_factor`.
+    ///
+    /// Panics unless `load_factor` lies strictly between 0 and 1, since a full
+    /// table would leave probing without an empty slot to stop at.
+    pub fn with_capacity_and_load_factor(capacity: usize, load_factor: f64) -> Self {
+        assert!(
+            load_factor > 0.0 && load_factor < 1.0,
+            "Load factor must be between 0 and 1, got: {}",
+            load_factor
+        );
+        Self::with_slots(
+            Self::slots_for(capacity, load_factor),
+            load_factor,
+            RandomState::new(),
+        )
+    }
+}
+
+impl<K: Hash + PartialEq, V, S: BuildHasher> FlatHashMap<K, V, S> {
+    pub fn put(&mut self, key: K, value: V) {
+        match self.entry(key) {
+            Entry::Occupied(mut entry) => {
+                entry.insert(value);
+            }
+            Entry::Vacant(entry) => {
+                entry.insert(value);
+            }
+        }
+    }
+
+    pub fn entry(&mut self, key: K) -> Entry<'_, K, V> {
+        self.reserve(1);
+        let hash = self.hash_key(&key);
+        match self.find_index(hash, &key) {
+            Some(index) => Entry::Occupied(OccupiedEntry {
+                key,
+                entry: self.slots[index].as_mut().unwrap(),
+            }),
+            None => Entry::Vacant(VacantEntry {
+                key,
+                hash,
+                slots: &mut self.slots,
+                size: &mut self.size,
+            }),
+        }
+    }
+
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + PartialEq + ?Sized,
+    {
+        self.get_key_value(key).map(|(_, value)| value)
+    }
+
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + PartialEq + ?Sized,
+    {
+        let index = self.find_index(self.hash_key(key), key)?;
+        self.slots[index].as_mut().map(|slot| &mut slot.value)
+    }
+
+    pub fn get_key_value<Q>(&self, key: &Q) -> Option<(&K, &V)>
+    where
+        K: Borrow<Q>,
+        Q: Hash + PartialEq + ?Sized,
+    {
+        let index = self.find_index(self.hash_key(key), key)?;
+        self.slots[index]
+            .as_ref()
+            .map(|slot| (&slot.key, &slot.value))
+    }
+
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + PartialEq + ?Sized,
+    {
+        self.find_index(self.hash_key(key), key).is_some()
+    }
+
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + PartialEq + ?Sized,
+    {
+        self.remove_entry(key).map(|(_, value)| value)
+    }
+
+    pub fn remove_entry<Q>(&mut self, key: &Q) -> Option<(K, V)>
+    where
+        K: Borrow<Q>,
+        Q: Hash + PartialEq + ?Sized,
+    {
+        let index = self.find_index(self.hash_key(key), key)?;
+        let slot = self.remove_at(index);
+        Some((slot.key, slot.value))
+    }
+
+    /// Removes every entry but keeps the current number of slots.
+    pub fn clear(&mut self) {
+        self.slots = Self::empty_slots(self.slots.len());
+        self.size = 0;
+    }
+
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter {
+            slots: self.slots.iter(),
+            remaining: self.size,
+        }
+    }
+
+    pub fn iter_mut(&mut self) -> IterMut<'_, K, V> {
+        IterMut {
+            slots: self.slots.iter_mut(),
+            remaining: self.size,
+        }
+    }
+
+    pub fn keys(&self) -> Keys<'_, K, V> {
+        Keys(self.iter())
+    }
+
+    pub fn values(&self) -> Values<'_, K, V> {
+        Values(self.iter())
+    }
+
+    pub fn values_mut(&mut self) -> ValuesMut<'_, K, V> {
+        ValuesMut(self.iter_mut())
+    }
+
+    /// Removes every entry, yielding them as owned pairs. The slot vector keeps its size.
+    pub fn drain(&mut self) -> Drain<'_, K, V> {
+        let slot_count = self.slots.len();
+        let slots = std::mem::replace(&mut self.slots, Self::empty_slots(slot_count));
+        let remaining = std::mem::replace(&mut self.size, 0);
+        Drain {
+            inner: IntoIter {
+                slots: slots.into_iter(),
+                remaining,
+            },
+            marker: PhantomData,
+        }
+    }
+
+    /// Keeps only the entries for which `keep` returns `true`, visiting each entry once.
+    pub fn retain<F>(&mut self, mut keep: F)
+    where
+        F: FnMut(&K, &mut V) -> bool,
+    {
+        let slot_count = self.slots.len();
+        let slots = std::mem::replace(&mut self.slots, Self::empty_slots(slot_count));
+        self.size = 0;
+        for mut slot in slots.into_iter().flatten() {
+            if keep(&slot.key, &mut slot.value) {
+                self.insert_slot(slot);
+            }
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.size
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
+    /// Number of entries the map can hold before the next rehash.
+    pub fn capacity(&self) -> usize {
+        (self.slots.len() as f64 * self.load_factor) as usize
+    }
+
+    pub fn load_factor(&self) -> f64 {
+        self.load_factor
+    }
+
+    /// Makes room for at least `additional` more entries without rehashing.
+    pub fn reserve(&mut self, additional: usize) {
+        let required = self.size + additional;
+        if required > self.capacity() {
+            let slot_count = Self::slots_for(required, self.load_factor);
+            self.resize(slot_count.max(self.slots.len() * 2));
+        }
+    }
+
+    /// Shrinks the slot vector to the smallest power of two that keeps the load
+    /// factor within its threshold.
+    pub fn shrink_to_fit(&mut self) {
+        let slot_count = Self::slots_for(self.size, self.load_factor);
+        if slot_count < self.slots.len() {
+            self.resize(slot_count);
+        }
+    }
+
+    pub fn with_hasher(hash_builder: S) -> Self {
+        Self::with_slots(DEFAULT_SLOT_COUNT, DEFAULT_LOAD_FACTOR, hash_builder)
+    }
+
+    pub fn with_capacity_and_hasher(capacity: usize, hash_builder: S) -> Self {
+        Self::with_slots(
+            Self::slots_for(capacity, DEFAULT_LOAD_FACTOR),
+            DEFAULT_LOAD_FACTOR,
+            hash_builder,
+        )
+    }
+
+    pub fn hasher(&self) -> &S {
+        &self.hash_builder
+    }
+
+    fn with_slots(slot_count: usize, load_factor: f64, hash_builder: S) -> Self {
+        FlatHashMap {
+            slots: Self::empty_slots(slot_count),
+            size: 0,
+            load_factor,
+            hash_builder,
+        }
+    }
+
+    fn empty_slots(slot_count: usize) -> Vec<Option<Slot<K, V>>> {
+        let mut vec = Vec::with_capacity(slot_count);
+        for _ in 0..slot_count {
+            vec.push(None);
+        }
+        vec
+    }
+
+    fn slots_for(capacity: usize, load_factor: f64) -> usize {
+        let mut slot_count = ((capacity as f64 / load_factor).ceil() as usize)
+            .max(1)
+            .next_power_of_two();
+        // Always leave at least one empty slot so that probing terminates
+        while ((slot_count as f64 * load_factor) as usize) < capacity || slot_count <= capacity {
+            slot_count *= 2;
+        }
+        slot_count
+    }
+
+    fn resize(&mut self, slot_count: usize) {
+        let old_slots = std::mem::replace(&mut self.slots, Self::empty_slots(slot_count));
+        self.size = 0;
+        for slot in old_slots.into_iter().flatten() {
+            self.insert_slot(slot);
+        }
+    }
+
+    fn mask(&self) -> usize {
+        self.slots.len() - 1
+    }
+
+    fn probe_distance(&self, hash: u64, index: usize) -> usize {
+        probe_distance(self.mask(), hash, index)
+    }
+
+    fn find_index<Q>(&self, hash: u64, key: &Q) -> Option<usize>
+    where
+        K: Borrow<Q>,
+        Q: PartialEq + ?Sized,
+    {
+        let mask = self.mask();
+        let mut index = hash as usize & mask;
+        let mut distance = 0;
+        while let Some(slot) = &self.slots[index] {
+            // Robin Hood ordering guarantees the key would have been placed before
+            // any entry that sits closer to its own ideal slot
+            if self.probe_distance(slot.hash, index) < distance || distance > mask {
+                return None;
+            }
+            if slot.hash == hash && slot.key.borrow() == key {
+                return Some(index);
+            }
+            index = (index + 1) & mask;
+            distance += 1;
+        }
+        None
+    }
+
+    /// Places a slot whose key is known to be absent and returns where it ended up.
+    /// The caller has to make sure there is room for it.
+    fn insert_slot(&mut self, slot: Slot<K, V>) -> usize {
+        self.size += 1;
+        insert_slot(&mut self.slots, slot)
+    }
+
+    /// Takes the slot at `index` out and shifts the rest of its probe run back by one.
+    fn remove_at(&mut self, index: usize) -> Slot<K, V> {
+        let mask = self.mask();
+        let removed = self.slots[index].take().unwrap();
+        let mut hole = index;
+        loop {
+            let next = (hole + 1) & mask;
+            match &self.slots[next] {
+                Some(slot) if self.probe_distance(slot.hash, next) > 0 => {
+                    self.slots[hole] = self.slots[next].take();
+                    hole = next;
+                }
+                _ => break,
+            }
+        }
+        self.size -= 1;
+        removed
+    }
+
+    fn hash_key<Q: Hash + ?Sized>(&self, key: &Q) -> u64 {
+        self.hash_builder.hash_one(key)
+    }
+}
+
+/// How far the entry stored at `index` is from the slot its hash points to.
+fn probe_distance(mask: usize, hash: u64, index: usize) -> usize {
+    index.wrapping_sub(hash as usize) & mask
+}
+
+// Robin Hood insertion into a table with at least one empty slot, shared by the map and
+// `VacantEntry`, which only borrows the slots. Does not count the new entry.
+fn insert_slot<K, V>(slots: &mut [Option<Slot<K, V>>], slot: Slot<K, V>) -> usize {
+    let mask = slots.len() - 1;
+    let mut index = slot.hash as usize & mask;
+    let mut distance = 0;
+    let mut carried = slot;
+    let mut placed_at = None;
+    loop {
+        let existing_distance = match &slots[index] {
+            Some(existing) => probe_distance(mask, existing.hash, index),
+            None => {
+                slots[index] = Some(carried);
+                return placed_at.unwrap_or(index);
+            }
+        };
+        if existing_distance < distance {
+            let existing = slots[index].as_mut().unwrap();
+            std::mem::swap(existing, &mut carried);
+            placed_at.get_or_insert(index);
+            distance = existing_distance;
+        }
+        index = (index + 1) & mask;
+        distance += 1;
+    }
+}
+
+impl<K, Q, V, S> Index<&Q> for FlatHashMap<K, V, S>
+where
+    K: Hash + PartialEq + Borrow<Q>,
+    Q: Hash + PartialEq + ?Sized,
+    S: BuildHasher,
+{
+    type Output = V;
+
+    /// Panics if the key is not present in the map.
+    fn index(&self, key: &Q) -> &V {
+        self.get(key)
+            .expect("Key is not present in the FlatHashMap")
+    }
+}
+
+impl<K: Hash + PartialEq + Debug, V: Debug, S> Debug for FlatHashMap<K, V, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let slots = self.slots.iter().flatten();
+        f.debug_map()
+            .entries(slots.map(|slot| (&slot.key, &slot.value)))
+            .finish()
+    }
+}
+
+/// Maps are equal when they hold the same pairs, regardless of slot layout or hasher.
+impl<K: Hash + PartialEq, V: PartialEq, S: BuildHasher> PartialEq for FlatHashMap<K, V, S> {
+    fn eq(&self, other: &Self) -> bool {
+        self.size == other.size
+            && self
+                .iter()
+                .all(|(key, value)| other.get(key) == Some(value))
+    }
+}
+
+impl<K: Hash + Eq, V: Eq, S: BuildHasher> Eq for FlatHashMap<K, V, S> {}
+
+impl<K: Hash + PartialEq, V, S: BuildHasher + Default> FromIterator<(K, V)>
+    for FlatHashMap<K, V, S>
+{
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut map = FlatHashMap::with_hasher(S::default());
+        map.extend(iter);
+        map
+    }
+}
+
+impl<K: Hash + PartialEq, V, S: BuildHasher> Extend<(K, V)> for FlatHashMap<K, V, S> {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        let iter = iter.into_iter();
+        self.reserve(iter.size_hint().0);
+        for (key, value) in iter {
+            self.put(key, value);
+        }
+    }
+}
+
+impl<'a, K: Hash + PartialEq, V, S: BuildHasher> IntoIterator for &'a FlatHashMap<K, V, S> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, K: Hash + PartialEq, V, S: BuildHasher> IntoIterator for &'a mut FlatHashMap<K, V, S> {
+    type Item = (&'a K, &'a mut V);
+    type IntoIter = IterMut<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl<K: Hash + PartialEq, V, S> IntoIterator for FlatHashMap<K, V, S> {
+    type Item = (K, V);
+    type IntoIter = IntoIter<K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter {
+            slots: self.slots.into_iter(),
+            remaining: self.size,
+        }
+    }
+}
+
+pub struct Iter<'a, K, V> {
+    slots: slice::Iter<'a, Option<Slot<K, V>>>,
+    remaining: usize,
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let slot = self.slots.by_ref().flatten().next()?;
+        self.remaining -= 1;
+        Some((&slot.key, &slot.value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<'a, K, V> ExactSizeIterator for Iter<'a, K, V> {}
+
+pub struct IterMut<'a, K, V> {
+    slots: slice::IterMut<'a, Option<Slot<K, V>>>,
+    remaining: usize,
+}
+
+impl<'a, K, V> Iterator for IterMut<'a, K, V> {
+    type Item = (&'a K, &'a mut V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let slot = self.slots.by_ref().flatten().next()?;
+        self.remaining -= 1;
+        Some((&slot.key, &mut slot.value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<'a, K, V> ExactSizeIterator for IterMut<'a, K, V> {}
+
+pub struct IntoIter<K, V> {
+    slots: vec::IntoIter<Option<Slot<K, V>>>,
+    remaining: usize,
+}
+
+impl<K, V> Iterator for IntoIter<K, V> {
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let slot = self.slots.by_ref().flatten().next()?;
+        self.remaining -= 1;
+        Some((slot.key, slot.value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<K, V> ExactSizeIterator for IntoIter<K, V> {}
+
+pub struct Drain<'a, K, V> {
+    inner: IntoIter<K, V>,
+    marker: PhantomData<&'a mut Vec<Option<Slot<K, V>>>>,
+}
+
+impl<'a, K, V> Iterator for Drain<'a, K, V> {
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<'a, K, V> ExactSizeIterator for Drain<'a, K, V> {}
+
+pub struct Keys<'a, K, V>(Iter<'a, K, V>);
+
+impl<'a, K, V> Iterator for Keys<'a, K, V> {
+    type Item = &'a K;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|(key, _)| key)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<'a, K, V> ExactSizeIterator for Keys<'a, K, V> {}
+
+pub struct Values<'a, K, V>(Iter<'a, K, V>);
+
+impl<'a, K, V> Iterator for Values<'a, K, V> {
+    type Item = &'a V;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|(_, value)| value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<'a, K, V> ExactSizeIterator for Values<'a, K, V> {}
+
+pub struct ValuesMut<'a, K, V>(IterMut<'a, K, V>);
+
+impl<'a, K, V> Iterator for ValuesMut<'a, K, V> {
+    type Item = &'a mut V;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|(_, value)| value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<'a, K, V> ExactSizeIterator for ValuesMut<'a, K, V> {}
+
+pub enum Entry<'a, K: Hash + PartialEq, V> {
+    Occupied(OccupiedEntry<'a, K, V>),
+    Vacant(VacantEntry<'a, K, V>),
+}
+
+pub struct OccupiedEntry<'a, K, V> {
+    key: K,
+    entry: &'a mut Slot<K, V>,
+}
+
+pub struct VacantEntry<'a, K: Hash + PartialEq, V> {
+    key: K,
+    hash: u64,
+    slots: &'a mut Vec<Option<Slot<K, V>>>,
+    size: &'a mut usize,
+}
+
+impl<'a, K: Hash + PartialEq, V> Entry<'a, K, V> {
+    pub fn key(&self) -> &K {
+        match self {
+            Entry::Occupied(entry) => entry.key(),
+            Entry::Vacant(entry) => entry.key(),
+        }
+    }
+
+    pub fn or_insert(self, default: V) -> &'a mut V {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(default),
+        }
+    }
+
+    pub fn or_insert_with<F: FnOnce() -> V>(self, default: F) -> &'a mut V {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(default()),
+        }
+    }
+
+    pub fn or_insert_with_key<F: FnOnce(&K) -> V>(self, default: F) -> &'a mut V {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => {
+                let value = default(entry.key());
+                entry.insert(value)
+            }
+        }
+    }
+
+    pub fn and_modify<F: FnOnce(&mut V)>(mut self, modify: F) -> Self {
+        if let Entry::Occupied(ref mut entry) = self {
+            modify(entry.get_mut());
+        }
+        self
+    }
+
+    pub fn or_default(self) -> &'a mut V
+    where
+        V: Default,
+    {
+        self.or_insert_with(V::default)
+    }
+}
+
+impl<'a, K, V> OccupiedEntry<'a, K, V> {
+    /// Returns the key stored in the map, not the one passed to `entry`.
+    pub fn key(&self) -> &K {
+        &self.entry.key
+    }
+
+    pub fn get(&self) -> &V {
+        &self.entry.value
+    }
+
+    pub fn get_mut(&mut self) -> &mut V {
+        &mut self.entry.value
+    }
+
+    pub fn into_mut(self) -> &'a mut V {
+        &mut self.entry.value
+    }
+
+    /// Replaces the value, returning the old one. The stored key is kept.
+    pub fn insert(&mut self, value: V) -> V {
+        std::mem::replace(&mut self.entry.value, value)
+    }
+
+    /// Hands back the key that was passed to `entry`.
+    pub fn into_key(self) -> K {
+        self.key
+    }
+}
+
+impl<'a, K: Hash + PartialEq, V> VacantEntry<'a, K, V> {
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    pub fn into_key(self) -> K {
+        self.key
+    }
+
+    pub fn insert(self, value: V) -> &'a mut V {
+        *self.size += 1;
+        let index = insert_slot(
+            self.slots,
+            Slot {
+                hash: self.hash,
+                key: self.key,
+                value,
+            },
+        );
+        &mut self.slots[index].as_mut().unwrap().value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::flat_hashmap::{Entry, FlatHashMap};
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{BuildHasherDefault, Hasher};
+
+    #[test]
+    fn put_get() {
+        let mut map: FlatHashMap<i32, i32> = FlatHashMap::new();
+        map.put(1, 1);
+        assert_eq!(map.len(), 1);
+        map.put(2, 3);
+        assert_eq!(map.len(), 2);
+        map.put(4, 5);
+        assert_eq!(map.len(), 3);
+        assert_eq!(*map.get(&1).unwrap(), 1);
+        assert_eq!(*map.get(&2).unwrap(), 3);
+        assert_eq!(*map.get(&4).unwrap(), 5);
+        assert_eq!(map.get(&3), None);
+    }
+
+    #[test]
+    fn rewrite_value() {
+        let mut map: FlatHashMap<i32, i32> = FlatHashMap::new();
+        map.put(1, 1);
+        map.put(2, 3);
+        map.put(1, 2);
+        assert_eq!(map.len(), 2);
+        assert_eq!(*map.get(&1).unwrap(), 2);
+        assert_eq!(*map.get(&2).unwrap(), 3);
+    }
+
+    #[test]
+    fn remove() {
+        let mut map: FlatHashMap<i32, i32> = FlatHashMap::new();
+        map.put(1, 1);
+        map.put(2, 3);
+        map.put(4, 5);
+        assert_eq!(map.remove(&1), Some(1));
+        assert_eq!(map.remove(&1), None);
+        assert_eq!(map.len(), 2);
+        assert_eq!(map.get(&1), None);
+        assert_eq!(*map.get(&2).unwrap(), 3);
+        assert_eq!(*map.get(&4).unwrap(), 5);
+        assert_eq!(map.remove_entry(&4), Some((4, 5)));
+        assert_eq!(map.remove(&2), Some(3));
+        assert!(map.is_empty());
+    }
+
+    #[derive(Default)]
+    struct ConstantHasher;
+
+    impl Hasher for ConstantHasher {
+        fn finish(&self) -> u64 {
+            7
+        }
+
+        fn write(&mut self, _bytes: &[u8]) {}
+    }
+
+    #[test]
+    fn colliding_keys() {
+        let mut map: FlatHashMap<i32, i32, BuildHasherDefault<ConstantHasher>> =
+            FlatHashMap::with_hasher(BuildHasherDefault::default());
+        for i in 0..40 {
+            map.put(i, i * 2);
+        }
+        assert_eq!(map.len(), 40);
+        for i in 0..40 {
+            assert_eq!(*map.get(&i).unwrap(), i * 2);
+        }
+        // Removing from the middle of the probe run must shift the rest back
+        for i in (0..40).step_by(3) {
+            assert_eq!(map.remove(&i), Some(i * 2));
+        }
+        for i in 0..40 {
+            if i % 3 == 0 {
+                assert_eq!(map.get(&i), None);
+            } else {
+                assert_eq!(*map.get(&i).unwrap(), i * 2);
+            }
+        }
+    }
+
+    #[test]
+    fn probe_distances_stay_ordered() {
+        let mut map: FlatHashMap<u32, u32> = FlatHashMap::with_capacity(16);
+        for i in 0..1000 {
+            map.put(i, i);
+            if i % 4 == 0 {
+                map.remove(&(i / 2));
+            }
+        }
+        let mask = map.slots.len() - 1;
+        for index in 0..map.slots.len() {
+            let next = (index + 1) & mask;
+            if let (Some(current), Some(following)) = (&map.slots[index], &map.slots[next]) {
+                // A following entry can be at most one step further from home
+                assert!(
+                    map.probe_distance(following.hash, next)
+                        <= map.probe_distance(current.hash, index) + 1
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn matches_std_hashmap() {
+        let mut map: FlatHashMap<u64, u64> = FlatHashMap::with_capacity(4);
+        let mut reference = std::collections::HashMap::new();
+        let mut seed: u64 = 42;
+        for _ in 0..5000 {
+            seed = seed
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            let key = (seed >> 33) % 512;
+            if seed % 3 == 0 {
+                assert_eq!(map.remove(&key), reference.remove(&key));
+            } else {
+                map.put(key, seed);
+                reference.insert(key, seed);
+            }
+            assert_eq!(map.len(), reference.len());
+        }
+        for (key, value) in &reference {
+            assert_eq!(map.get(key), Some(value));
+        }
+        assert_eq!(map.iter().count(), reference.len());
+    }
+
+    #[test]
+    fn grows_and_shrinks() {
+        let mut map: FlatHashMap<i32, i32> = FlatHashMap::with_capacity_and_load_factor(4, 0.5);
+        assert_eq!(map.capacity(), 4);
+        for i in 0..100 {
+            map.put(i, i);
+        }
+        assert!(map.capacity() >= 100);
+        assert!(map.slots.len().is_power_of_two());
+        for i in 3..100 {
+            map.remove(&i);
+        }
+        map.shrink_to_fit();
+        assert!(map.slots.len() <= 8);
+        assert_eq!(*map.get(&0).unwrap(), 0);
+        assert_eq!(*map.get(&1).unwrap(), 1);
+        assert_eq!(*map.get(&2).unwrap(), 2);
+        map.reserve(100);
+        assert!(map.capacity() >= 103);
+    }
+
+    #[test]
+    #[should_panic]
+    fn full_load_factor() {
+        let _map: FlatHashMap<i32, i32> = FlatHashMap::with_capacity_and_load_factor(4, 1.0);
+    }
+
+    #[test]
+    fn entry() {
+        let mut map: FlatHashMap<char, usize> = FlatHashMap::with_capacity(1);
+        for letter in "hello world".chars() {
+            *map.entry(letter).or_default() += 1;
+        }
+        assert_eq!(map[&'l'], 3);
+        assert_eq!(map[&'o'], 2);
+        map.entry('h').and_modify(|count| *count += 10).or_insert(0);
+        assert_eq!(map[&'h'], 11);
+        match map.entry('z') {
+            Entry::Occupied(_) => panic!("Entry must be vacant"),
+            Entry::Vacant(entry) => assert_eq!(*entry.insert(5), 5),
+        }
+        match map.entry('z') {
+            Entry::Occupied(mut entry) => assert_eq!(entry.insert(6), 5),
+            Entry::Vacant(_) => panic!("Entry must be occupied"),
+        }
+        assert_eq!(map[&'z'], 6);
+    }
+
+    #[test]
+    fn entry_type_ignores_the_hasher() {
+        fn bump(entry: Entry<'_, &'static str, i32>) {
+            *entry.or_insert(0) += 1;
+        }
+        let mut map: FlatHashMap<&str, i32, BuildHasherDefault<DefaultHasher>> =
+            FlatHashMap::default();
+        bump(map.entry("a"));
+        bump(map.entry("a"));
+        let mut other = FlatHashMap::new();
+        bump(other.entry("a"));
+        assert_eq!(map["a"], 2);
+        assert_eq!(other["a"], 1);
+    }
+
+    #[test]
+    fn iteration() {
+        let mut map: FlatHashMap<i32, i32> = (0..50).map(|i| (i, i)).collect();
+        for value in map.values_mut() {
+            *value *= 2;
+        }
+        let mut pairs: Vec<(i32, i32)> = map.iter().map(|(key, value)| (*key, *value)).collect();
+        pairs.sort();
+        assert_eq!(pairs, (0..50).map(|i| (i, i * 2)).collect::<Vec<_>>());
+        assert_eq!(map.keys().len(), 50);
+        assert_eq!(map.values().sum::<i32>(), 2450);
+        let mut owned: Vec<(i32, i32)> = map.into_iter().collect();
+        owned.sort();
+        assert_eq!(owned.len(), 50);
+    }
+
+    #[test]
+    fn drain_and_retain() {
+        let mut map: FlatHashMap<i32, i32> = (0..30).map(|i| (i, i)).collect();
+        map.retain(|key, _| key % 3 == 0);
+        assert_eq!(map.len(), 10);
+        assert!(map.contains_key(&3));
+        assert!(!map.contains_key(&4));
+        let mut drained: Vec<i32> = map.drain().map(|(key, _)| key).collect();
+        drained.sort();
+        assert_eq!(drained, (0..30).step_by(3).collect::<Vec<_>>());
+        assert!(map.is_empty());
+        map.extend(vec![(1, 1)]);
+        assert_eq!(map[&1], 1);
+    }
+
+    #[test]
+    fn borrowed_lookups() {
+        let mut map: FlatHashMap<String, i32, BuildHasherDefault<DefaultHasher>> =
+            FlatHashMap::default();
+        map.put("one".to_string(), 1);
+        assert_eq!(map.get("one"), Some(&1));
+        assert_eq!(map.get_key_value("one"), Some((&"one".to_string(), &1)));
+        *map.get_mut("one").unwrap() += 1;
+        assert_eq!(map["one"], 2);
+        map.clear();
+        assert_eq!(map.get("one"), None);
+    }
+
+    #[test]
+    fn clone_eq_and_debug() {
+        let mut map: FlatHashMap<&str, i32> = FlatHashMap::new();
+        map.put("a", 1);
+        map.put("b", 2);
+        let mut copy = map.clone();
+        assert_eq!(map, copy);
+        copy.put("b", 3);
+        assert_ne!(map, copy);
+        assert_eq!(map["b"], 2);
+        // Equality ignores slot layout
+        let mut other = FlatHashMap::with_capacity(100);
+        other.put("b", 2);
+        other.put("a", 1);
+        assert_eq!(map, other);
+        map.remove("a");
+        assert_eq!(format!("{:?}", map), r#"{"b": 2}"#);
+    }
+}
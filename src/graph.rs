@@ -1,6 +1,8 @@
 use crate::vector_based::VectorBasedDataStructure;
+use std::iter::FromIterator;
+use std::{slice, vec};
 
-#[derive(PartialEq, Debug)]
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
 pub struct GraphNode<'a, T> {
     value: T,
     lines: Vec<&'a GraphNode<'a, T>>,
@@ -15,7 +17,7 @@ impl<'a, T: PartialEq> GraphNode<'a, T> {
         self.value = new_value;
     }
 
-    pub fn get_lines(&self) -> &Vec<&GraphNode<T>> {
+    pub fn get_lines(&self) -> &Vec<&GraphNode<'_, T>> {
         &self.lines
     }
 
@@ -46,10 +48,55 @@ impl<'a, T: PartialEq> GraphNode<'a, T> {
     }
 }
 
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
 pub struct Graph<'a, T> {
     nodes: Vec<GraphNode<'a, T>>,
 }
 
+impl<'a, T> Graph<'a, T> {
+    pub fn iter(&self) -> slice::Iter<'_, GraphNode<'a, T>> {
+        self.nodes.iter()
+    }
+}
+
+impl<'a, T> Default for Graph<'a, T> {
+    fn default() -> Self {
+        Graph { nodes: Vec::new() }
+    }
+}
+
+impl<'a, T> FromIterator<GraphNode<'a, T>> for Graph<'a, T> {
+    fn from_iter<I: IntoIterator<Item = GraphNode<'a, T>>>(iter: I) -> Self {
+        Graph {
+            nodes: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl<'a, T> Extend<GraphNode<'a, T>> for Graph<'a, T> {
+    fn extend<I: IntoIterator<Item = GraphNode<'a, T>>>(&mut self, iter: I) {
+        self.nodes.extend(iter);
+    }
+}
+
+impl<'a, T> IntoIterator for Graph<'a, T> {
+    type Item = GraphNode<'a, T>;
+    type IntoIter = vec::IntoIter<GraphNode<'a, T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.nodes.into_iter()
+    }
+}
+
+impl<'a, 'g, T> IntoIterator for &'g Graph<'a, T> {
+    type Item = &'g GraphNode<'a, T>;
+    type IntoIter = slice::Iter<'g, GraphNode<'a, T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
 impl<'a, T: PartialEq> VectorBasedDataStructure<T> for Graph<'a, T> {
     fn new() -> Self {
         Graph { nodes: Vec::new() }
@@ -142,4 +189,34 @@ mod tests {
         graph_node.remove_line(&first_node);
         assert!(graph_node.get_lines().is_empty());
     }
+
+    #[test]
+    fn from_iter_and_extend() {
+        let mut graph: Graph<i32> = (0..3).map(GraphNode::new).collect();
+        assert_eq!(graph.len(), 3);
+        graph.extend(vec![GraphNode::new(3)]);
+        let values: Vec<i32> = graph.iter().map(|node| *node.get_value()).collect();
+        assert_eq!(values, vec![0, 1, 2, 3]);
+        assert_eq!((&graph).into_iter().count(), 4);
+        assert_eq!(graph.into_iter().last(), Some(GraphNode::new(3)));
+    }
+
+    #[test]
+    fn std_traits() {
+        use std::collections::HashSet;
+
+        let leaf = GraphNode::new(1);
+        let root = GraphNode::new_with_lines(0, vec![&leaf]);
+        let graph: Graph<i32> = vec![root.clone(), leaf.clone()].into_iter().collect();
+        let copy = graph.clone();
+        assert_eq!(graph, copy);
+        assert_ne!(graph, Graph::default());
+        assert_eq!(Graph::<i32>::default(), Graph::new());
+        assert_eq!(format!("{:?}", leaf), "GraphNode { value: 1, lines: [] }");
+        let set: HashSet<GraphNode<i32>> =
+            vec![root.clone(), root, leaf.clone()].into_iter().collect();
+        assert_eq!(set.len(), 2);
+        let graphs: HashSet<Graph<i32>> = vec![graph, copy].into_iter().collect();
+        assert_eq!(graphs.len(), 1);
+    }
 }
@@ -1,108 +1,662 @@
-use crate::linked_list::LinkedList;
-use std::collections::hash_map::DefaultHasher;
-use std::hash::{Hash, Hasher};
+use crate::linked_list::{self, LinkedList, VacantNode};
+use std::borrow::Borrow;
+use std::collections::hash_map::RandomState;
+use std::fmt::{self, Debug};
+use std::hash::{BuildHasher, Hash};
+use std::iter::FromIterator;
+use std::marker::PhantomData;
+use std::ops::Index;
+use std::{slice, vec};
 
-pub struct HashMap<K: Hash + PartialEq, V> {
-    buckets: Vec<LinkedList<Entry<K, V>>>,
+const DEFAULT_BUCKET_COUNT: usize = 32;
+const DEFAULT_LOAD_FACTOR: f64 = 0.75;
+
+/// Separate-chaining hash map. Keys are hashed with `S`, which defaults to a
+/// randomly seeded SipHash so that bucket placement can't be predicted from the keys.
+#[derive(Clone)]
+pub struct HashMap<K: Hash + PartialEq, V, S = RandomState> {
+    buckets: Vec<LinkedList<KeyValue<K, V>>>,
     size: usize,
+    load_factor: f64,
+    hash_builder: S,
 }
 
-struct Entry<K: Hash + PartialEq, V> {
+#[derive(Clone)]
+struct KeyValue<K, V> {
     key: K,
     value: V,
 }
 
+impl<K: Hash + PartialEq, V, S: BuildHasher + Default> Default for HashMap<K, V, S> {
+    fn default() -> Self {
+        Self::with_hasher(S::default())
+    }
+}
+
 impl<K: Hash + PartialEq, V> HashMap<K, V> {
+    pub fn new() -> Self {
+        Self::with_hasher(RandomState::new())
+    }
+
+    /// Creates a map able to hold at least `capacity` entries before it has to grow.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self::with_capacity_and_hasher(capacity, RandomState::new())
+    }
+
+    /// Creates a map able to hold at least `capacity` entries which grows once
+    /// the ratio of entries to buckets exceeds `load_factor`.
+    ///
+    /// Panics if `load_factor` is not a positive finite number.
+    pub fn with_capacity_and_load_factor(capacity: usize, load_factor: f64) -> Self {
+        assert!(
+            load_factor.is_finite() && load_factor > 0.0,
+            "Load factor must be a positive finite number, got: {}",
+            load_factor
+        );
+        Self::with_buckets(
+            Self::buckets_for(capacity, load_factor),
+            load_factor,
+            RandomState::new(),
+        )
+    }
+}
+
+impl<K: Hash + PartialEq, V, S: BuildHasher> HashMap<K, V, S> {
     pub fn put(&mut self, key: K, value: V) {
-        let index = self.key_to_index(&key);
-        let entry_list = &mut self.buckets[index];
-        if let Some(entry) = entry_list.iter_mut().find(|node| node.key == key) {
-            entry.value = value;
-        } else {
-            self.size += 1;
-            let new_entry = Entry { key, value };
-            entry_list.add(new_entry);
+        match self.entry(key) {
+            Entry::Occupied(mut entry) => {
+                entry.insert(value);
+            }
+            Entry::Vacant(entry) => {
+                entry.insert(value);
+            }
         }
     }
 
-    pub fn get(&self, key: &K) -> Option<&V> {
+    /// Looks up `key` with a single hash and a single walk over its bucket.
+    pub fn entry(&mut self, key: K) -> Entry<'_, K, V> {
+        self.reserve(1);
         let index = self.key_to_index(&key);
+        let HashMap { buckets, size, .. } = self;
+        match buckets[index].find_mut_or_vacant(|entry| entry.key == key) {
+            Ok(entry) => Entry::Occupied(OccupiedEntry { key, entry }),
+            Err(slot) => Entry::Vacant(VacantEntry { key, slot, size }),
+        }
+    }
+
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + PartialEq + ?Sized,
+    {
+        self.get_key_value(key).map(|(_, value)| value)
+    }
+
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + PartialEq + ?Sized,
+    {
+        let index = self.key_to_index(key);
+        self.buckets[index]
+            .iter_mut()
+            .find(|entry| entry.key.borrow() == key)
+            .map(|entry| &mut entry.value)
+    }
+
+    pub fn get_key_value<Q>(&self, key: &Q) -> Option<(&K, &V)>
+    where
+        K: Borrow<Q>,
+        Q: Hash + PartialEq + ?Sized,
+    {
+        let index = self.key_to_index(key);
         self.buckets[index]
             .iter()
-            .find(|entry| entry.key == *key)
-            .map(|entry| &entry.value)
+            .find(|entry| entry.key.borrow() == key)
+            .map(|entry| (&entry.key, &entry.value))
     }
 
-    pub fn remove(&mut self, key: &K) -> Option<V> {
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + PartialEq + ?Sized,
+    {
+        self.get_key_value(key).is_some()
+    }
+
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + PartialEq + ?Sized,
+    {
+        self.remove_entry(key).map(|(_, value)| value)
+    }
+
+    pub fn remove_entry<Q>(&mut self, key: &Q) -> Option<(K, V)>
+    where
+        K: Borrow<Q>,
+        Q: Hash + PartialEq + ?Sized,
+    {
         let index = self.key_to_index(key);
         self.buckets[index]
-            .delete_match(|entry| entry.key == *key)
+            .delete_match(|entry| entry.key.borrow() == key)
             .map(|entry| {
                 self.size -= 1;
-                entry.value
+                (entry.key, entry.value)
             })
     }
 
-    pub fn keys(&self) -> Vec<&K> {
-        let mut result = Vec::with_capacity(self.size);
-        for bucket in &self.buckets {
-            for entry_elem in bucket.iter() {
-                if result.contains(&&entry_elem.key) {
-                    continue;
-                }
-                result.push(&entry_elem.key);
-            }
+    /// Removes every entry but keeps the current number of buckets.
+    pub fn clear(&mut self) {
+        self.buckets = Self::empty_buckets(self.buckets.len());
+        self.size = 0;
+    }
+
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter {
+            buckets: self.buckets.iter(),
+            current: None,
+            remaining: self.size,
         }
-        result
     }
 
-    pub fn new() -> Self {
-        let initial_capacity = 32;
-        let mut vec = Vec::with_capacity(initial_capacity);
-        for i in 0..initial_capacity - 1 {
-            vec.insert(i, LinkedList::new())
+    pub fn iter_mut(&mut self) -> IterMut<'_, K, V> {
+        IterMut {
+            buckets: self.buckets.iter_mut(),
+            current: None,
+            remaining: self.size,
         }
-        HashMap {
-            buckets: vec,
-            size: 0,
+    }
+
+    pub fn keys(&self) -> Keys<'_, K, V> {
+        Keys(self.iter())
+    }
+
+    pub fn values(&self) -> Values<'_, K, V> {
+        Values(self.iter())
+    }
+
+    pub fn values_mut(&mut self) -> ValuesMut<'_, K, V> {
+        ValuesMut(self.iter_mut())
+    }
+
+    /// Removes every entry, yielding them as owned pairs. The bucket vector keeps its size.
+    pub fn drain(&mut self) -> Drain<'_, K, V> {
+        let bucket_count = self.buckets.len();
+        let buckets = std::mem::replace(&mut self.buckets, Self::empty_buckets(bucket_count));
+        let remaining = std::mem::replace(&mut self.size, 0);
+        Drain {
+            inner: IntoIter {
+                buckets: buckets.into_iter(),
+                current: None,
+                remaining,
+            },
+            marker: PhantomData,
+        }
+    }
+
+    /// Keeps only the entries for which `keep` returns `true`, visiting each entry once.
+    pub fn retain<F>(&mut self, mut keep: F)
+    where
+        F: FnMut(&K, &mut V) -> bool,
+    {
+        for bucket in &mut self.buckets {
+            let before = bucket.len();
+            bucket.retain_mut(|entry| keep(&entry.key, &mut entry.value));
+            self.size -= before - bucket.len();
+        }
+    }
+
+    pub fn with_hasher(hash_builder: S) -> Self {
+        Self::with_buckets(DEFAULT_BUCKET_COUNT, DEFAULT_LOAD_FACTOR, hash_builder)
+    }
+
+    pub fn with_capacity_and_hasher(capacity: usize, hash_builder: S) -> Self {
+        Self::with_buckets(
+            Self::buckets_for(capacity, DEFAULT_LOAD_FACTOR),
+            DEFAULT_LOAD_FACTOR,
+            hash_builder,
+        )
+    }
+
+    pub fn len(&self) -> usize {
+        self.size
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
+    /// Number of entries the map can hold before the next rehash.
+    pub fn capacity(&self) -> usize {
+        (self.buckets.len() as f64 * self.load_factor) as usize
+    }
+
+    pub fn load_factor(&self) -> f64 {
+        self.load_factor
+    }
+
+    /// Makes room for at least `additional` more entries without rehashing.
+    pub fn reserve(&mut self, additional: usize) {
+        let required = self
+            .size
+            .checked_add(additional)
+            .expect("capacity overflow");
+        if required > self.capacity() {
+            let bucket_count = Self::buckets_for(required, self.load_factor);
+            self.resize(bucket_count.max(self.buckets.len() * 2));
         }
     }
 
-    pub fn with_capacity(initial_capacity: usize) -> Self {
-        let mut vec = Vec::with_capacity(initial_capacity);
-        for i in 0..initial_capacity {
-            vec.insert(i, LinkedList::new())
+    /// Shrinks the bucket vector to the smallest size that keeps the load
+    /// factor within its threshold.
+    pub fn shrink_to_fit(&mut self) {
+        let bucket_count = Self::buckets_for(self.size, self.load_factor);
+        if bucket_count < self.buckets.len() {
+            self.resize(bucket_count);
         }
+    }
+
+    pub fn hasher(&self) -> &S {
+        &self.hash_builder
+    }
+
+    fn with_buckets(bucket_count: usize, load_factor: f64, hash_builder: S) -> Self {
         HashMap {
-            buckets: vec,
+            buckets: Self::empty_buckets(bucket_count),
             size: 0,
+            load_factor,
+            hash_builder,
         }
     }
 
-    pub fn len(&self) -> usize {
-        self.size
+    fn empty_buckets(bucket_count: usize) -> Vec<LinkedList<KeyValue<K, V>>> {
+        let mut vec = Vec::with_capacity(bucket_count);
+        for _ in 0..bucket_count {
+            vec.push(LinkedList::new());
+        }
+        vec
+    }
+
+    fn buckets_for(capacity: usize, load_factor: f64) -> usize {
+        // No vector of buckets can take up more than isize::MAX bytes
+        let max_buckets =
+            isize::MAX as usize / std::mem::size_of::<LinkedList<KeyValue<K, V>>>().max(1);
+        let bucket_count = (capacity as f64 / load_factor).ceil();
+        assert!(bucket_count <= max_buckets as f64, "capacity overflow");
+        let mut bucket_count = bucket_count as usize;
+        // Rounding may leave the capacity just short of the requested one
+        while ((bucket_count as f64 * load_factor) as usize) < capacity {
+            assert!(bucket_count < max_buckets, "capacity overflow");
+            bucket_count += 1;
+        }
+        bucket_count.max(1)
+    }
+
+    fn resize(&mut self, bucket_count: usize) {
+        let old_buckets = std::mem::replace(&mut self.buckets, Self::empty_buckets(bucket_count));
+        for bucket in old_buckets {
+            for entry in bucket {
+                let index = self.key_to_index(&entry.key);
+                self.buckets[index].add_first(entry);
+            }
+        }
     }
 
-    fn key_to_index(&self, key: &K) -> usize {
+    fn key_to_index<Q: Hash + ?Sized>(&self, key: &Q) -> usize {
         self.hash_key(key) % self.buckets.len()
     }
 
-    fn hash_key(&self, key: &K) -> usize {
-        let mut hasher = DefaultHasher::new();
-        key.hash(&mut hasher);
-        hasher.finish() as usize
+    fn hash_key<Q: Hash + ?Sized>(&self, key: &Q) -> usize {
+        self.hash_builder.hash_one(key) as usize
     }
 }
 
-impl<K: Hash + PartialEq, V> PartialEq for Entry<K, V> {
+impl<K, Q, V, S> Index<&Q> for HashMap<K, V, S>
+where
+    K: Hash + PartialEq + Borrow<Q>,
+    Q: Hash + PartialEq + ?Sized,
+    S: BuildHasher,
+{
+    type Output = V;
+
+    /// Panics if the key is not present in the map.
+    fn index(&self, key: &Q) -> &V {
+        self.get(key).expect("Key is not present in the HashMap")
+    }
+}
+
+impl<K: Hash + PartialEq + Debug, V: Debug, S> Debug for HashMap<K, V, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let entries = self.buckets.iter().flat_map(|bucket| bucket.iter());
+        f.debug_map()
+            .entries(entries.map(|entry| (&entry.key, &entry.value)))
+            .finish()
+    }
+}
+
+/// Maps are equal when they hold the same pairs, regardless of bucket layout or hasher.
+impl<K: Hash + PartialEq, V: PartialEq, S: BuildHasher> PartialEq for HashMap<K, V, S> {
     fn eq(&self, other: &Self) -> bool {
-        self.key == other.key
+        self.size == other.size
+            && self
+                .iter()
+                .all(|(key, value)| other.get(key) == Some(value))
+    }
+}
+
+impl<K: Hash + Eq, V: Eq, S: BuildHasher> Eq for HashMap<K, V, S> {}
+
+impl<K: Hash + PartialEq, V, S: BuildHasher + Default> FromIterator<(K, V)> for HashMap<K, V, S> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut map = HashMap::with_hasher(S::default());
+        map.extend(iter);
+        map
+    }
+}
+
+impl<K: Hash + PartialEq, V, S: BuildHasher> Extend<(K, V)> for HashMap<K, V, S> {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        let iter = iter.into_iter();
+        self.reserve(iter.size_hint().0);
+        for (key, value) in iter {
+            self.put(key, value);
+        }
+    }
+}
+
+impl<'a, K: Hash + PartialEq, V, S: BuildHasher> IntoIterator for &'a HashMap<K, V, S> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, K: Hash + PartialEq, V, S: BuildHasher> IntoIterator for &'a mut HashMap<K, V, S> {
+    type Item = (&'a K, &'a mut V);
+    type IntoIter = IterMut<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl<K: Hash + PartialEq, V, S> IntoIterator for HashMap<K, V, S> {
+    type Item = (K, V);
+    type IntoIter = IntoIter<K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter {
+            buckets: self.buckets.into_iter(),
+            current: None,
+            remaining: self.size,
+        }
+    }
+}
+
+pub struct Iter<'a, K: Hash + PartialEq, V> {
+    buckets: slice::Iter<'a, LinkedList<KeyValue<K, V>>>,
+    current: Option<linked_list::Iter<'a, KeyValue<K, V>>>,
+    remaining: usize,
+}
+
+impl<'a, K: Hash + PartialEq, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(entry) = self.current.as_mut().and_then(|bucket| bucket.next()) {
+                self.remaining -= 1;
+                return Some((&entry.key, &entry.value));
+            }
+            self.current = Some(self.buckets.next()?.iter());
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<'a, K: Hash + PartialEq, V> ExactSizeIterator for Iter<'a, K, V> {}
+
+pub struct IterMut<'a, K: Hash + PartialEq, V> {
+    buckets: slice::IterMut<'a, LinkedList<KeyValue<K, V>>>,
+    current: Option<linked_list::IterMut<'a, KeyValue<K, V>>>,
+    remaining: usize,
+}
+
+impl<'a, K: Hash + PartialEq, V> Iterator for IterMut<'a, K, V> {
+    type Item = (&'a K, &'a mut V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(entry) = self.current.as_mut().and_then(|bucket| bucket.next()) {
+                self.remaining -= 1;
+                return Some((&entry.key, &mut entry.value));
+            }
+            self.current = Some(self.buckets.next()?.iter_mut());
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<'a, K: Hash + PartialEq, V> ExactSizeIterator for IterMut<'a, K, V> {}
+
+pub struct IntoIter<K: Hash + PartialEq, V> {
+    buckets: vec::IntoIter<LinkedList<KeyValue<K, V>>>,
+    current: Option<linked_list::IntoIter<KeyValue<K, V>>>,
+    remaining: usize,
+}
+
+impl<K: Hash + PartialEq, V> Iterator for IntoIter<K, V> {
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(entry) = self.current.as_mut().and_then(|bucket| bucket.next()) {
+                self.remaining -= 1;
+                return Some((entry.key, entry.value));
+            }
+            self.current = Some(self.buckets.next()?.into_iter());
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<K: Hash + PartialEq, V> ExactSizeIterator for IntoIter<K, V> {}
+
+pub struct Drain<'a, K: Hash + PartialEq, V> {
+    inner: IntoIter<K, V>,
+    marker: PhantomData<&'a mut HashMap<K, V>>,
+}
+
+impl<'a, K: Hash + PartialEq, V> Iterator for Drain<'a, K, V> {
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<'a, K: Hash + PartialEq, V> ExactSizeIterator for Drain<'a, K, V> {}
+
+pub struct Keys<'a, K: Hash + PartialEq, V>(Iter<'a, K, V>);
+
+impl<'a, K: Hash + PartialEq, V> Iterator for Keys<'a, K, V> {
+    type Item = &'a K;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|(key, _)| key)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<'a, K: Hash + PartialEq, V> ExactSizeIterator for Keys<'a, K, V> {}
+
+pub struct Values<'a, K: Hash + PartialEq, V>(Iter<'a, K, V>);
+
+impl<'a, K: Hash + PartialEq, V> Iterator for Values<'a, K, V> {
+    type Item = &'a V;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|(_, value)| value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<'a, K: Hash + PartialEq, V> ExactSizeIterator for Values<'a, K, V> {}
+
+pub struct ValuesMut<'a, K: Hash + PartialEq, V>(IterMut<'a, K, V>);
+
+impl<'a, K: Hash + PartialEq, V> Iterator for ValuesMut<'a, K, V> {
+    type Item = &'a mut V;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|(_, value)| value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<'a, K: Hash + PartialEq, V> ExactSizeIterator for ValuesMut<'a, K, V> {}
+
+pub enum Entry<'a, K: Hash + PartialEq, V> {
+    Occupied(OccupiedEntry<'a, K, V>),
+    Vacant(VacantEntry<'a, K, V>),
+}
+
+pub struct OccupiedEntry<'a, K: Hash + PartialEq, V> {
+    key: K,
+    entry: &'a mut KeyValue<K, V>,
+}
+
+pub struct VacantEntry<'a, K: Hash + PartialEq, V> {
+    key: K,
+    slot: VacantNode<'a, KeyValue<K, V>>,
+    size: &'a mut usize,
+}
+
+impl<'a, K: Hash + PartialEq, V> Entry<'a, K, V> {
+    pub fn key(&self) -> &K {
+        match self {
+            Entry::Occupied(entry) => entry.key(),
+            Entry::Vacant(entry) => entry.key(),
+        }
+    }
+
+    pub fn or_insert(self, default: V) -> &'a mut V {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(default),
+        }
+    }
+
+    pub fn or_insert_with<F: FnOnce() -> V>(self, default: F) -> &'a mut V {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(default()),
+        }
+    }
+
+    pub fn or_insert_with_key<F: FnOnce(&K) -> V>(self, default: F) -> &'a mut V {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => {
+                let value = default(entry.key());
+                entry.insert(value)
+            }
+        }
+    }
+
+    pub fn and_modify<F: FnOnce(&mut V)>(mut self, modify: F) -> Self {
+        if let Entry::Occupied(ref mut entry) = self {
+            modify(entry.get_mut());
+        }
+        self
+    }
+
+    pub fn or_default(self) -> &'a mut V
+    where
+        V: Default,
+    {
+        self.or_insert_with(V::default)
+    }
+}
+
+impl<'a, K: Hash + PartialEq, V> OccupiedEntry<'a, K, V> {
+    /// Returns the key stored in the map, not the one passed to `entry`.
+    pub fn key(&self) -> &K {
+        &self.entry.key
+    }
+
+    pub fn get(&self) -> &V {
+        &self.entry.value
+    }
+
+    pub fn get_mut(&mut self) -> &mut V {
+        &mut self.entry.value
+    }
+
+    pub fn into_mut(self) -> &'a mut V {
+        &mut self.entry.value
+    }
+
+    /// Replaces the value, returning the old one. The stored key is kept.
+    pub fn insert(&mut self, value: V) -> V {
+        std::mem::replace(&mut self.entry.value, value)
+    }
+
+    /// Hands back the key that was passed to `entry`.
+    pub fn into_key(self) -> K {
+        self.key
+    }
+}
+
+impl<'a, K: Hash + PartialEq, V> VacantEntry<'a, K, V> {
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    pub fn into_key(self) -> K {
+        self.key
+    }
+
+    pub fn insert(self, value: V) -> &'a mut V {
+        *self.size += 1;
+        let entry = self.slot.insert(KeyValue {
+            key: self.key,
+            value,
+        });
+        &mut entry.value
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::hashmap::HashMap;
+    use crate::hashmap::{Entry, HashMap};
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{BuildHasher, BuildHasherDefault, Hasher};
 
     #[test]
     fn put_get() {
@@ -179,10 +733,11 @@ mod tests {
 
     #[test]
     fn single_bucket() {
-        let mut map: HashMap<i32, i32> = HashMap::with_capacity(1);
+        let mut map: HashMap<i32, i32> = HashMap::with_capacity_and_load_factor(3, 3.0);
         map.put(1, 1);
         map.put(2, 3);
         map.put(4, 5);
+        assert_eq!(map.buckets.len(), 1);
         assert_eq!(map.len(), 3);
         assert_eq!(*map.get(&1).unwrap(), 1);
         assert_eq!(*map.get(&2).unwrap(), 3);
@@ -201,16 +756,413 @@ mod tests {
     // TODO: Write a similar test with the keys' hashcode collision to ensure no duplicates result
     #[test]
     fn keys() {
-        let mut map: HashMap<&str, i32> = HashMap::with_capacity(10);
+        let mut map: HashMap<&str, i32> = HashMap::with_capacity_and_load_factor(10, 1.0);
         map.put("key1", 1);
         map.put("key2", 2);
         map.put("key3", 3);
         map.put("key4", 4);
-        let mut keys = map.keys().into_iter();
+        let keys = map.keys();
         assert_eq!(keys.len(), 4);
-        assert_eq!(*keys.next().unwrap(), "key4");
-        assert_eq!(*keys.next().unwrap(), "key3");
-        assert_eq!(*keys.next().unwrap(), "key2");
-        assert_eq!(*keys.next().unwrap(), "key1");
+        // Bucket order depends on the randomly seeded hasher
+        let mut keys: Vec<&&str> = keys.collect();
+        keys.sort();
+        assert_eq!(keys, vec![&"key1", &"key2", &"key3", &"key4"]);
+    }
+
+    #[test]
+    fn grows_past_load_factor() {
+        let mut map: HashMap<i32, i32> = HashMap::with_capacity_and_load_factor(4, 1.0);
+        assert_eq!(map.capacity(), 4);
+        assert_eq!(map.buckets.len(), 4);
+        for i in 0..100 {
+            map.put(i, i * 10);
+        }
+        assert_eq!(map.len(), 100);
+        assert!(map.capacity() >= 100);
+        assert!(map.len() as f64 / map.buckets.len() as f64 <= map.load_factor());
+        for i in 0..100 {
+            assert_eq!(*map.get(&i).unwrap(), i * 10);
+        }
+    }
+
+    #[test]
+    fn with_capacity_and_load_factor() {
+        let map: HashMap<i32, i32> = HashMap::with_capacity_and_load_factor(10, 0.5);
+        assert_eq!(map.load_factor(), 0.5);
+        assert_eq!(map.buckets.len(), 20);
+        assert_eq!(map.capacity(), 10);
+        let map: HashMap<i32, i32> = HashMap::with_capacity(0);
+        assert_eq!(map.buckets.len(), 1);
+        assert_eq!(map.get(&1), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn invalid_load_factor() {
+        let _map: HashMap<i32, i32> = HashMap::with_capacity_and_load_factor(10, 0.0);
+    }
+
+    #[test]
+    fn reserve() {
+        let mut map: HashMap<i32, i32> = HashMap::with_capacity(2);
+        map.put(1, 1);
+        map.reserve(50);
+        assert!(map.capacity() >= 51);
+        let bucket_count = map.buckets.len();
+        for i in 2..=51 {
+            map.put(i, i);
+        }
+        assert_eq!(map.buckets.len(), bucket_count);
+        assert_eq!(map.len(), 51);
+        assert_eq!(*map.get(&1).unwrap(), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "capacity overflow")]
+    fn reserve_overflow() {
+        let mut map: HashMap<i32, i32> = HashMap::new();
+        map.put(1, 1);
+        map.reserve(usize::MAX);
+    }
+
+    #[test]
+    #[should_panic(expected = "capacity overflow")]
+    fn with_capacity_overflow() {
+        let _map: HashMap<i32, i32> = HashMap::with_capacity_and_load_factor(usize::MAX, 0.5);
+    }
+
+    #[test]
+    fn shrink_to_fit() {
+        let mut map: HashMap<i32, i32> = HashMap::with_capacity(1000);
+        map.put(1, 10);
+        map.put(2, 20);
+        map.put(3, 30);
+        map.shrink_to_fit();
+        assert!(map.capacity() >= 3);
+        assert!(map.buckets.len() < 10);
+        assert_eq!(*map.get(&1).unwrap(), 10);
+        assert_eq!(*map.get(&2).unwrap(), 20);
+        assert_eq!(*map.get(&3).unwrap(), 30);
+        map.remove(&1);
+        map.remove(&2);
+        map.remove(&3);
+        map.shrink_to_fit();
+        assert_eq!(map.buckets.len(), 1);
+        map.put(4, 40);
+        assert_eq!(*map.get(&4).unwrap(), 40);
+    }
+
+    #[test]
+    fn entry_or_insert() {
+        let mut map: HashMap<&str, i32> = HashMap::new();
+        *map.entry("a").or_insert(1) += 10;
+        assert_eq!(map.len(), 1);
+        assert_eq!(*map.get(&"a").unwrap(), 11);
+        *map.entry("a").or_insert(1) += 10;
+        assert_eq!(map.len(), 1);
+        assert_eq!(*map.get(&"a").unwrap(), 21);
+        assert_eq!(*map.entry("b").or_insert_with(|| 5), 5);
+        assert_eq!(
+            *map.entry("c").or_insert_with_key(|key| key.len() as i32),
+            1
+        );
+        assert_eq!(map.len(), 3);
+    }
+
+    #[test]
+    fn entry_and_modify() {
+        let mut map: HashMap<&str, i32> = HashMap::new();
+        map.entry("a").and_modify(|value| *value += 1).or_insert(1);
+        assert_eq!(*map.get(&"a").unwrap(), 1);
+        map.entry("a").and_modify(|value| *value += 1).or_insert(1);
+        assert_eq!(*map.get(&"a").unwrap(), 2);
+        assert_eq!(map.len(), 1);
+    }
+
+    #[test]
+    fn entry_or_default() {
+        let mut map: HashMap<char, usize> = HashMap::with_capacity(1);
+        for letter in "hello world".chars() {
+            *map.entry(letter).or_default() += 1;
+        }
+        assert_eq!(map.len(), 8);
+        assert_eq!(*map.get(&'l').unwrap(), 3);
+        assert_eq!(*map.get(&'o').unwrap(), 2);
+        assert_eq!(*map.get(&'h').unwrap(), 1);
+    }
+
+    #[test]
+    fn entry_variants() {
+        let mut map: HashMap<i32, i32> = HashMap::new();
+        match map.entry(1) {
+            Entry::Occupied(_) => panic!("Entry must be vacant"),
+            Entry::Vacant(entry) => {
+                assert_eq!(entry.key(), &1);
+                assert_eq!(*entry.insert(10), 10);
+            }
+        }
+        match map.entry(1) {
+            Entry::Occupied(mut entry) => {
+                assert_eq!(entry.key(), &1);
+                assert_eq!(entry.get(), &10);
+                assert_eq!(entry.insert(20), 10);
+                *entry.get_mut() += 1;
+            }
+            Entry::Vacant(_) => panic!("Entry must be occupied"),
+        }
+        assert_eq!(map.len(), 1);
+        assert_eq!(*map.get(&1).unwrap(), 21);
+    }
+
+    #[test]
+    fn iter() {
+        let mut map: HashMap<i32, i32> = HashMap::with_capacity(3);
+        assert_eq!(map.iter().next(), None);
+        for i in 0..50 {
+            map.put(i, i * 2);
+        }
+        let iter = map.iter();
+        assert_eq!(iter.len(), 50);
+        let mut pairs: Vec<(i32, i32)> = iter.map(|(key, value)| (*key, *value)).collect();
+        pairs.sort();
+        assert_eq!(pairs, (0..50).map(|i| (i, i * 2)).collect::<Vec<_>>());
+        let mut borrowed = 0;
+        for (key, value) in &map {
+            assert_eq!(*value, key * 2);
+            borrowed += 1;
+        }
+        assert_eq!(borrowed, 50);
+    }
+
+    #[test]
+    fn iter_mut() {
+        let mut map: HashMap<i32, i32> = (0..10).map(|i| (i, i)).collect();
+        for (key, value) in map.iter_mut() {
+            *value += key;
+        }
+        for (_, value) in &mut map {
+            *value += 1;
+        }
+        for i in 0..10 {
+            assert_eq!(*map.get(&i).unwrap(), i * 2 + 1);
+        }
+    }
+
+    #[test]
+    fn values() {
+        let mut map: HashMap<&str, i32> = HashMap::new();
+        map.put("a", 1);
+        map.put("b", 2);
+        map.put("c", 3);
+        assert_eq!(map.values().sum::<i32>(), 6);
+        for value in map.values_mut() {
+            *value *= 10;
+        }
+        let mut values: Vec<&i32> = map.values().collect();
+        values.sort();
+        assert_eq!(values, vec![&10, &20, &30]);
+    }
+
+    #[test]
+    fn into_iter() {
+        let map: HashMap<i32, &str> = vec![(1, "one"), (2, "two"), (3, "three")]
+            .into_iter()
+            .collect();
+        let iter = map.into_iter();
+        assert_eq!(iter.len(), 3);
+        let mut pairs: Vec<(i32, &str)> = iter.collect();
+        pairs.sort();
+        assert_eq!(pairs, vec![(1, "one"), (2, "two"), (3, "three")]);
+    }
+
+    #[test]
+    fn drain() {
+        let mut map: HashMap<i32, i32> = (0..20).map(|i| (i, -i)).collect();
+        let mut drained: Vec<(i32, i32)> = map.drain().collect();
+        drained.sort();
+        assert_eq!(drained, (0..20).map(|i| (i, -i)).collect::<Vec<_>>());
+        assert_eq!(map.len(), 0);
+        assert_eq!(map.get(&1), None);
+        map.put(1, 1);
+        assert_eq!(*map.get(&1).unwrap(), 1);
+    }
+
+    #[test]
+    fn retain() {
+        let mut map: HashMap<i32, i32> = HashMap::with_capacity_and_load_factor(1, 100.0);
+        map.extend((0..30).map(|i| (i, i)));
+        map.retain(|key, value| {
+            *value *= 2;
+            key % 3 == 0
+        });
+        assert_eq!(map.len(), 10);
+        for i in 0..30 {
+            if i % 3 == 0 {
+                assert_eq!(*map.get(&i).unwrap(), i * 2);
+            } else {
+                assert_eq!(map.get(&i), None);
+            }
+        }
+    }
+
+    #[test]
+    fn from_iter_and_extend() {
+        let mut map: HashMap<&str, i32> = vec![("a", 1), ("b", 2)].into_iter().collect();
+        assert_eq!(map.len(), 2);
+        map.extend(vec![("b", 20), ("c", 30)]);
+        assert_eq!(map.len(), 3);
+        assert_eq!(*map.get(&"a").unwrap(), 1);
+        assert_eq!(*map.get(&"b").unwrap(), 20);
+        assert_eq!(*map.get(&"c").unwrap(), 30);
+    }
+
+    #[derive(Default)]
+    struct ConstantHasher;
+
+    impl Hasher for ConstantHasher {
+        fn finish(&self) -> u64 {
+            7
+        }
+
+        fn write(&mut self, _bytes: &[u8]) {}
+    }
+
+    #[test]
+    fn with_hasher() {
+        let mut map: HashMap<i32, i32, BuildHasherDefault<ConstantHasher>> =
+            HashMap::with_hasher(BuildHasherDefault::default());
+        for i in 0..20 {
+            map.put(i, i + 100);
+        }
+        assert_eq!(map.len(), 20);
+        // Every key collides, so a single bucket holds the whole map
+        assert_eq!(
+            map.buckets
+                .iter()
+                .filter(|bucket| !bucket.is_empty())
+                .count(),
+            1
+        );
+        for i in 0..20 {
+            assert_eq!(*map.get(&i).unwrap(), i + 100);
+        }
+        assert_eq!(map.remove(&5).unwrap(), 105);
+        assert_eq!(map.get(&5), None);
+        assert_eq!(map.len(), 19);
+    }
+
+    #[test]
+    fn with_capacity_and_hasher() {
+        let mut map: HashMap<&str, i32, BuildHasherDefault<DefaultHasher>> =
+            HashMap::with_capacity_and_hasher(100, BuildHasherDefault::default());
+        assert!(map.capacity() >= 100);
+        map.put("a", 1);
+        map.put("b", 2);
+        assert_eq!(*map.get(&"a").unwrap(), 1);
+        assert_eq!(*map.get(&"b").unwrap(), 2);
+        let default_map: HashMap<&str, i32, BuildHasherDefault<DefaultHasher>> = HashMap::default();
+        assert!(default_map.is_empty());
+    }
+
+    #[test]
+    fn random_seed_per_map() {
+        let first: HashMap<i32, i32> = HashMap::new();
+        let second: HashMap<i32, i32> = HashMap::new();
+        let hashes = |map: &HashMap<i32, i32>| -> Vec<u64> {
+            (0..8)
+                .map(|key| {
+                    let mut hasher = map.hasher().build_hasher();
+                    hasher.write_i32(key);
+                    hasher.finish()
+                })
+                .collect()
+        };
+        assert_ne!(hashes(&first), hashes(&second));
+    }
+
+    #[test]
+    fn borrowed_lookups() {
+        let mut map: HashMap<String, i32> = HashMap::new();
+        map.put("one".to_string(), 1);
+        map.put("two".to_string(), 2);
+        assert_eq!(map.get("one"), Some(&1));
+        assert!(map.contains_key("two"));
+        assert!(!map.contains_key("three"));
+        assert_eq!(map.get_key_value("two"), Some((&"two".to_string(), &2)));
+        assert_eq!(map.remove("one"), Some(1));
+        assert_eq!(map.get("one"), None);
+        assert_eq!(map.len(), 1);
+    }
+
+    #[test]
+    fn get_mut() {
+        let mut map: HashMap<&str, Vec<i32>> = HashMap::new();
+        map.put("list", vec![1]);
+        map.get_mut("list").unwrap().push(2);
+        assert_eq!(map.get("list").unwrap(), &vec![1, 2]);
+        assert_eq!(map.get_mut("missing"), None);
+    }
+
+    #[test]
+    fn remove_entry() {
+        let mut map: HashMap<String, i32> = HashMap::new();
+        map.put("key".to_string(), 5);
+        assert_eq!(map.remove_entry("key"), Some(("key".to_string(), 5)));
+        assert_eq!(map.remove_entry("key"), None);
+        assert!(map.is_empty());
+    }
+
+    #[test]
+    fn clear() {
+        let mut map: HashMap<i32, i32> = (0..10).map(|i| (i, i)).collect();
+        assert!(!map.is_empty());
+        map.clear();
+        assert!(map.is_empty());
+        assert_eq!(map.get(&1), None);
+        assert_eq!(map.iter().count(), 0);
+        map.put(1, 2);
+        assert_eq!(map[&1], 2);
+    }
+
+    #[test]
+    fn index() {
+        let mut map: HashMap<String, i32> = HashMap::new();
+        map.put("a".to_string(), 1);
+        assert_eq!(map["a"], 1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn index_missing_key() {
+        let map: HashMap<String, i32> = HashMap::new();
+        let _ = map["missing"];
+    }
+
+    #[test]
+    fn clone_and_eq() {
+        let mut map: HashMap<&str, i32> = HashMap::new();
+        map.put("a", 1);
+        map.put("b", 2);
+        let mut copy = map.clone();
+        assert_eq!(map, copy);
+        copy.put("b", 3);
+        assert_ne!(map, copy);
+        assert_eq!(map["b"], 2);
+        // Equality ignores bucket layout
+        let mut other = HashMap::with_capacity_and_load_factor(100, 0.5);
+        other.put("b", 2);
+        other.put("a", 1);
+        assert_eq!(map, other);
+        other.put("c", 3);
+        assert_ne!(map, other);
+        assert_eq!(HashMap::<i32, i32>::default(), HashMap::new());
+    }
+
+    #[test]
+    fn debug() {
+        let mut map: HashMap<&str, i32> = HashMap::new();
+        assert_eq!(format!("{:?}", map), "{}");
+        map.put("a", 1);
+        assert_eq!(format!("{:?}", map), r#"{"a": 1}"#);
     }
 }
@@ -0,0 +1,495 @@
+use crate::hashmap::{self, Entry, HashMap};
+use std::borrow::Borrow;
+use std::collections::hash_map::RandomState;
+use std::fmt::{self, Debug};
+use std::hash::{BuildHasher, Hash};
+use std::iter::FromIterator;
+use std::ops::{BitAnd, BitOr, BitXor, Sub};
+
+#[derive(Clone)]
+pub struct HashSet<K: Hash + PartialEq, S = RandomState> {
+    map: HashMap<K, (), S>,
+}
+
+impl<K: Hash + PartialEq, S: BuildHasher + Default> Default for HashSet<K, S> {
+    fn default() -> Self {
+        Self::with_hasher(S::default())
+    }
+}
+
+impl<K: Hash + PartialEq> HashSet<K> {
+    pub fn new() -> Self {
+        HashSet {
+            map: HashMap::new(),
+        }
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        HashSet {
+            map: HashMap::with_capacity(capacity),
+        }
+    }
+}
+
+impl<K: Hash + PartialEq, S: BuildHasher> HashSet<K, S> {
+    pub fn with_hasher(hash_builder: S) -> Self {
+        HashSet {
+            map: HashMap::with_hasher(hash_builder),
+        }
+    }
+
+    pub fn with_capacity_and_hasher(capacity: usize, hash_builder: S) -> Self {
+        HashSet {
+            map: HashMap::with_capacity_and_hasher(capacity, hash_builder),
+        }
+    }
+
+    /// Adds `value` to the set, returning `false` if it was already present.
+    pub fn insert(&mut self, value: K) -> bool {
+        match self.map.entry(value) {
+            Entry::Occupied(_) => false,
+            Entry::Vacant(entry) => {
+                entry.insert(());
+                true
+            }
+        }
+    }
+
+    pub fn contains<Q>(&self, value: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + PartialEq + ?Sized,
+    {
+        self.map.contains_key(value)
+    }
+
+    pub fn get<Q>(&self, value: &Q) -> Option<&K>
+    where
+        K: Borrow<Q>,
+        Q: Hash + PartialEq + ?Sized,
+    {
+        self.map.get_key_value(value).map(|(key, _)| key)
+    }
+
+    /// Removes `value` from the set, returning `false` if it was not present.
+    pub fn remove<Q>(&mut self, value: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + PartialEq + ?Sized,
+    {
+        self.map.remove(value).is_some()
+    }
+
+    pub fn take<Q>(&mut self, value: &Q) -> Option<K>
+    where
+        K: Borrow<Q>,
+        Q: Hash + PartialEq + ?Sized,
+    {
+        self.map.remove_entry(value).map(|(key, _)| key)
+    }
+
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.map.capacity()
+    }
+
+    pub fn reserve(&mut self, additional: usize) {
+        self.map.reserve(additional);
+    }
+
+    pub fn shrink_to_fit(&mut self) {
+        self.map.shrink_to_fit();
+    }
+
+    pub fn clear(&mut self) {
+        self.map.clear();
+    }
+
+    pub fn iter(&self) -> Iter<'_, K> {
+        Iter(self.map.keys())
+    }
+
+    pub fn drain(&mut self) -> Drain<'_, K> {
+        Drain(self.map.drain())
+    }
+
+    pub fn retain<F>(&mut self, mut keep: F)
+    where
+        F: FnMut(&K) -> bool,
+    {
+        self.map.retain(|key, _| keep(key));
+    }
+
+    /// Lazily yields every value that is in `self` or `other`, each only once.
+    pub fn union<'a>(&'a self, other: &'a HashSet<K, S>) -> Union<'a, K, S> {
+        Union {
+            first: self.iter(),
+            second: other.difference(self),
+        }
+    }
+
+    /// Lazily yields the values that are both in `self` and `other`.
+    pub fn intersection<'a>(&'a self, other: &'a HashSet<K, S>) -> Intersection<'a, K, S> {
+        Intersection {
+            iter: self.iter(),
+            other,
+        }
+    }
+
+    /// Lazily yields the values that are in `self` but not in `other`.
+    pub fn difference<'a>(&'a self, other: &'a HashSet<K, S>) -> Difference<'a, K, S> {
+        Difference {
+            iter: self.iter(),
+            other,
+        }
+    }
+
+    /// Lazily yields the values that are in exactly one of `self` and `other`.
+    pub fn symmetric_difference<'a>(
+        &'a self,
+        other: &'a HashSet<K, S>,
+    ) -> SymmetricDifference<'a, K, S> {
+        SymmetricDifference {
+            first: self.difference(other),
+            second: other.difference(self),
+        }
+    }
+
+    pub fn is_subset(&self, other: &HashSet<K, S>) -> bool {
+        self.len() <= other.len() && self.iter().all(|value| other.contains(value))
+    }
+
+    pub fn is_superset(&self, other: &HashSet<K, S>) -> bool {
+        other.is_subset(self)
+    }
+
+    pub fn is_disjoint(&self, other: &HashSet<K, S>) -> bool {
+        self.intersection(other).next().is_none()
+    }
+}
+
+impl<K: Hash + PartialEq, S: BuildHasher> PartialEq for HashSet<K, S> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.is_subset(other)
+    }
+}
+
+impl<K: Hash + Eq, S: BuildHasher> Eq for HashSet<K, S> {}
+
+impl<K: Hash + PartialEq + Debug, S: BuildHasher> Debug for HashSet<K, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl<K: Hash + PartialEq, S: BuildHasher + Default> FromIterator<K> for HashSet<K, S> {
+    fn from_iter<I: IntoIterator<Item = K>>(iter: I) -> Self {
+        let mut set = HashSet::with_hasher(S::default());
+        set.extend(iter);
+        set
+    }
+}
+
+impl<K: Hash + PartialEq, S: BuildHasher> Extend<K> for HashSet<K, S> {
+    fn extend<I: IntoIterator<Item = K>>(&mut self, iter: I) {
+        self.map.extend(iter.into_iter().map(|value| (value, ())));
+    }
+}
+
+impl<'a, K: Hash + PartialEq, S: BuildHasher> IntoIterator for &'a HashSet<K, S> {
+    type Item = &'a K;
+    type IntoIter = Iter<'a, K>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<K: Hash + PartialEq, S> IntoIterator for HashSet<K, S> {
+    type Item = K;
+    type IntoIter = IntoIter<K>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter(self.map.into_iter())
+    }
+}
+
+impl<K, S> BitOr<&HashSet<K, S>> for &HashSet<K, S>
+where
+    K: Hash + PartialEq + Clone,
+    S: BuildHasher + Default,
+{
+    type Output = HashSet<K, S>;
+
+    fn bitor(self, other: &HashSet<K, S>) -> HashSet<K, S> {
+        self.union(other).cloned().collect()
+    }
+}
+
+impl<K, S> BitAnd<&HashSet<K, S>> for &HashSet<K, S>
+where
+    K: Hash + PartialEq + Clone,
+    S: BuildHasher + Default,
+{
+    type Output = HashSet<K, S>;
+
+    fn bitand(self, other: &HashSet<K, S>) -> HashSet<K, S> {
+        self.intersection(other).cloned().collect()
+    }
+}
+
+impl<K, S> Sub<&HashSet<K, S>> for &HashSet<K, S>
+where
+    K: Hash + PartialEq + Clone,
+    S: BuildHasher + Default,
+{
+    type Output = HashSet<K, S>;
+
+    fn sub(self, other: &HashSet<K, S>) -> HashSet<K, S> {
+        self.difference(other).cloned().collect()
+    }
+}
+
+impl<K, S> BitXor<&HashSet<K, S>> for &HashSet<K, S>
+where
+    K: Hash + PartialEq + Clone,
+    S: BuildHasher + Default,
+{
+    type Output = HashSet<K, S>;
+
+    fn bitxor(self, other: &HashSet<K, S>) -> HashSet<K, S> {
+        self.symmetric_difference(other).cloned().collect()
+    }
+}
+
+pub struct Iter<'a, K: Hash + PartialEq>(hashmap::Keys<'a, K, ()>);
+
+impl<'a, K: Hash + PartialEq> Iterator for Iter<'a, K> {
+    type Item = &'a K;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<'a, K: Hash + PartialEq> ExactSizeIterator for Iter<'a, K> {}
+
+pub struct IntoIter<K: Hash + PartialEq>(hashmap::IntoIter<K, ()>);
+
+impl<K: Hash + PartialEq> Iterator for IntoIter<K> {
+    type Item = K;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|(key, _)| key)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<K: Hash + PartialEq> ExactSizeIterator for IntoIter<K> {}
+
+pub struct Drain<'a, K: Hash + PartialEq>(hashmap::Drain<'a, K, ()>);
+
+impl<'a, K: Hash + PartialEq> Iterator for Drain<'a, K> {
+    type Item = K;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|(key, _)| key)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<'a, K: Hash + PartialEq> ExactSizeIterator for Drain<'a, K> {}
+
+pub struct Union<'a, K: Hash + PartialEq, S> {
+    first: Iter<'a, K>,
+    second: Difference<'a, K, S>,
+}
+
+impl<'a, K: Hash + PartialEq, S: BuildHasher> Iterator for Union<'a, K, S> {
+    type Item = &'a K;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.first.next().or_else(|| self.second.next())
+    }
+}
+
+pub struct Intersection<'a, K: Hash + PartialEq, S> {
+    iter: Iter<'a, K>,
+    other: &'a HashSet<K, S>,
+}
+
+impl<'a, K: Hash + PartialEq, S: BuildHasher> Iterator for Intersection<'a, K, S> {
+    type Item = &'a K;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let other = self.other;
+        self.iter.by_ref().find(|value| other.contains(*value))
+    }
+}
+
+pub struct Difference<'a, K: Hash + PartialEq, S> {
+    iter: Iter<'a, K>,
+    other: &'a HashSet<K, S>,
+}
+
+impl<'a, K: Hash + PartialEq, S: BuildHasher> Iterator for Difference<'a, K, S> {
+    type Item = &'a K;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let other = self.other;
+        self.iter.by_ref().find(|value| !other.contains(*value))
+    }
+}
+
+pub struct SymmetricDifference<'a, K: Hash + PartialEq, S> {
+    first: Difference<'a, K, S>,
+    second: Difference<'a, K, S>,
+}
+
+impl<'a, K: Hash + PartialEq, S: BuildHasher> Iterator for SymmetricDifference<'a, K, S> {
+    type Item = &'a K;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.first.next().or_else(|| self.second.next())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::hashset::HashSet;
+
+    fn sorted<'a, I: Iterator<Item = &'a i32>>(iter: I) -> Vec<i32> {
+        let mut values: Vec<i32> = iter.cloned().collect();
+        values.sort();
+        values
+    }
+
+    #[test]
+    fn insert_contains_remove() {
+        let mut set: HashSet<i32> = HashSet::new();
+        assert!(set.is_empty());
+        assert!(set.insert(1));
+        assert!(set.insert(2));
+        assert!(!set.insert(1));
+        assert_eq!(set.len(), 2);
+        assert!(set.contains(&1));
+        assert!(!set.contains(&3));
+        assert!(set.remove(&1));
+        assert!(!set.remove(&1));
+        assert_eq!(set.len(), 1);
+        assert_eq!(set.take(&2), Some(2));
+        assert!(set.is_empty());
+    }
+
+    #[test]
+    fn borrowed_lookups() {
+        let mut set: HashSet<String> = HashSet::new();
+        set.insert("apple".to_string());
+        assert!(set.contains("apple"));
+        assert_eq!(set.get("apple"), Some(&"apple".to_string()));
+        assert!(set.remove("apple"));
+    }
+
+    #[test]
+    fn iteration() {
+        let mut set: HashSet<i32> = (0..10).collect();
+        assert_eq!(set.iter().len(), 10);
+        assert_eq!(sorted(set.iter()), (0..10).collect::<Vec<_>>());
+        set.retain(|value| value % 2 == 0);
+        assert_eq!(sorted(set.iter()), vec![0, 2, 4, 6, 8]);
+        let mut drained: Vec<i32> = set.drain().collect();
+        drained.sort();
+        assert_eq!(drained, vec![0, 2, 4, 6, 8]);
+        assert!(set.is_empty());
+        set.extend(vec![3, 3, 4]);
+        let mut owned: Vec<i32> = set.into_iter().collect();
+        owned.sort();
+        assert_eq!(owned, vec![3, 4]);
+    }
+
+    #[test]
+    fn union() {
+        let first: HashSet<i32> = vec![1, 2, 3].into_iter().collect();
+        let second: HashSet<i32> = vec![3, 4, 5].into_iter().collect();
+        assert_eq!(sorted(first.union(&second)), vec![1, 2, 3, 4, 5]);
+        assert_eq!(sorted((&first | &second).iter()), vec![1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn intersection() {
+        let first: HashSet<i32> = vec![1, 2, 3].into_iter().collect();
+        let second: HashSet<i32> = vec![2, 3, 4].into_iter().collect();
+        assert_eq!(sorted(first.intersection(&second)), vec![2, 3]);
+        assert_eq!(sorted((&first & &second).iter()), vec![2, 3]);
+    }
+
+    #[test]
+    fn difference() {
+        let first: HashSet<i32> = vec![1, 2, 3].into_iter().collect();
+        let second: HashSet<i32> = vec![2, 3, 4].into_iter().collect();
+        assert_eq!(sorted(first.difference(&second)), vec![1]);
+        assert_eq!(sorted(second.difference(&first)), vec![4]);
+        assert_eq!(sorted((&first - &second).iter()), vec![1]);
+    }
+
+    #[test]
+    fn symmetric_difference() {
+        let first: HashSet<i32> = vec![1, 2, 3].into_iter().collect();
+        let second: HashSet<i32> = vec![2, 3, 4].into_iter().collect();
+        assert_eq!(sorted(first.symmetric_difference(&second)), vec![1, 4]);
+        assert_eq!(sorted((&first ^ &second).iter()), vec![1, 4]);
+    }
+
+    #[test]
+    fn subset_and_disjoint() {
+        let small: HashSet<i32> = vec![1, 2].into_iter().collect();
+        let large: HashSet<i32> = vec![1, 2, 3].into_iter().collect();
+        let other: HashSet<i32> = vec![7, 8].into_iter().collect();
+        assert!(small.is_subset(&large));
+        assert!(!large.is_subset(&small));
+        assert!(large.is_superset(&small));
+        assert!(small.is_disjoint(&other));
+        assert!(!small.is_disjoint(&large));
+        let empty: HashSet<i32> = HashSet::new();
+        assert!(empty.is_subset(&small));
+        assert!(empty.is_disjoint(&small));
+    }
+
+    #[test]
+    fn equality() {
+        let first: HashSet<i32> = vec![1, 2, 3].into_iter().collect();
+        let second: HashSet<i32> = vec![3, 2, 1, 1].into_iter().collect();
+        let third: HashSet<i32> = vec![1, 2].into_iter().collect();
+        assert_eq!(first, second);
+        assert_ne!(first, third);
+        let expected: HashSet<i32> = vec![3].into_iter().collect();
+        assert_eq!(&first - &third, expected);
+    }
+
+    #[test]
+    fn clone_and_debug() {
+        let set: HashSet<i32> = vec![7].into_iter().collect();
+        let mut copy = set.clone();
+        assert_eq!(set, copy);
+        copy.insert(8);
+        assert_ne!(set, copy);
+        assert_eq!(format!("{:?}", set), "{7}");
+    }
+}
@@ -1,9 +1,24 @@
+pub mod cache;
+pub mod deque;
+pub mod doubly_linked_list;
+pub mod error;
+pub mod expiring_map;
+pub mod flat_hashmap;
 pub mod graph;
 pub mod hashmap;
+pub mod hashset;
+pub mod linked_hashmap;
 pub mod linked_list;
 pub mod linked_stack;
+pub mod mergeable_heap;
+pub mod priority_queue;
 pub mod queue;
+pub mod queue_like;
+pub mod ring_buffer;
 pub mod stack;
+pub mod stack_like;
+#[cfg(test)]
+mod test_util;
 pub mod vector_based;
 
 #[cfg(test)]
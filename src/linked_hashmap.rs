@@ -0,0 +1,717 @@
+use std::borrow::Borrow;
+use std::collections::hash_map::RandomState;
+use std::fmt::{self, Debug};
+use std::hash::{BuildHasher, Hash};
+use std::iter::FromIterator;
+
+const DEFAULT_BUCKET_COUNT: usize = 16;
+
+/// Hash map that remembers the order of its entries.
+///
+/// Entries are kept in a slab and threaded onto two kinds of lists by index: a doubly
+/// linked ordering list, and one singly linked chain per hash bucket that the lookups
+/// walk. Each key is stored once, in its slab node. By default the ordering list follows
+/// insertion order. A map created with `with_access_order` also moves an entry to the
+/// back whenever `get`, `get_mut` or `put` uses it, so iteration goes from least to most
+/// recently used. `peek` reads an entry without moving it.
+#[derive(Clone)]
+pub struct LinkedHashMap<K: Hash + PartialEq, V, S = RandomState> {
+    buckets: Vec<Option<usize>>,
+    nodes: Vec<Option<Node<K, V>>>,
+    free: Vec<usize>,
+    len: usize,
+    head: Option<usize>,
+    tail: Option<usize>,
+    access_order: bool,
+    hash_builder: S,
+}
+
+#[derive(Clone)]
+struct Node<K, V> {
+    key: K,
+    value: V,
+    hash: u64,
+    chain: Option<usize>,
+    prev: Option<usize>,
+    next: Option<usize>,
+}
+
+impl<K: Hash + PartialEq, V, S: BuildHasher + Default> Default for LinkedHashMap<K, V, S> {
+    fn default() -> Self {
+        Self::with_hasher(S::default())
+    }
+}
+
+impl<K: Hash + PartialEq, V> LinkedHashMap<K, V> {
+    pub fn new() -> Self {
+        Self::with_hasher(RandomState::new())
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self::with_capacity_and_hasher(capacity, RandomState::new())
+    }
+
+    /// Creates a map whose order is updated on every access instead of only on insertion.
+    pub fn with_access_order() -> Self {
+        let mut map = Self::new();
+        map.access_order = true;
+        map
+    }
+}
+
+impl<K: Hash + PartialEq, V, S: BuildHasher> LinkedHashMap<K, V, S> {
+    pub fn with_hasher(hash_builder: S) -> Self {
+        Self::with_buckets(DEFAULT_BUCKET_COUNT, Vec::new(), hash_builder)
+    }
+
+    pub fn with_capacity_and_hasher(capacity: usize, hash_builder: S) -> Self {
+        Self::with_buckets(
+            Self::buckets_for(capacity),
+            Vec::with_capacity(capacity),
+            hash_builder,
+        )
+    }
+
+    pub fn hasher(&self) -> &S {
+        &self.hash_builder
+    }
+
+    pub fn is_access_order(&self) -> bool {
+        self.access_order
+    }
+
+    /// Inserts or updates an entry. A new key goes to the back of the order; an existing
+    /// one keeps its place unless the map is in access order.
+    pub fn put(&mut self, key: K, value: V) {
+        if let Some(node_index) = self.find(&key) {
+            self.node_mut(node_index).value = value;
+            self.touch(node_index);
+            return;
+        }
+        let hash = self.hash_builder.hash_one(&key);
+        let bucket = self.bucket(hash);
+        let node = Node {
+            key,
+            value,
+            hash,
+            chain: self.buckets[bucket],
+            prev: None,
+            next: None,
+        };
+        let node_index = match self.free.pop() {
+            Some(free_index) => {
+                self.nodes[free_index] = Some(node);
+                free_index
+            }
+            None => {
+                self.nodes.push(Some(node));
+                self.nodes.len() - 1
+            }
+        };
+        self.buckets[bucket] = Some(node_index);
+        self.len += 1;
+        self.link_back(node_index);
+        if self.len > Self::capacity_of(self.buckets.len()) {
+            self.rehash(self.buckets.len() * 2);
+        }
+    }
+
+    pub fn get<Q>(&mut self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + PartialEq + ?Sized,
+    {
+        let node_index = self.find(key)?;
+        self.touch(node_index);
+        Some(&self.node(node_index).value)
+    }
+
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + PartialEq + ?Sized,
+    {
+        let node_index = self.find(key)?;
+        self.touch(node_index);
+        Some(&mut self.node_mut(node_index).value)
+    }
+
+    /// Looks up a value without counting it as an access.
+    pub fn peek<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + PartialEq + ?Sized,
+    {
+        let node_index = self.find(key)?;
+        Some(&self.node(node_index).value)
+    }
+
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + PartialEq + ?Sized,
+    {
+        self.find(key).is_some()
+    }
+
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + PartialEq + ?Sized,
+    {
+        self.remove_entry(key).map(|(_, value)| value)
+    }
+
+    pub fn remove_entry<Q>(&mut self, key: &Q) -> Option<(K, V)>
+    where
+        K: Borrow<Q>,
+        Q: Hash + PartialEq + ?Sized,
+    {
+        let node_index = self.find(key)?;
+        self.pop_at(node_index)
+    }
+
+    pub fn front(&self) -> Option<(&K, &V)> {
+        self.head
+            .map(|node_index| self.node(node_index))
+            .map(|node| (&node.key, &node.value))
+    }
+
+    pub fn back(&self) -> Option<(&K, &V)> {
+        self.tail
+            .map(|node_index| self.node(node_index))
+            .map(|node| (&node.key, &node.value))
+    }
+
+    pub fn pop_front(&mut self) -> Option<(K, V)> {
+        let node_index = self.head?;
+        self.pop_at(node_index)
+    }
+
+    pub fn pop_back(&mut self) -> Option<(K, V)> {
+        let node_index = self.tail?;
+        self.pop_at(node_index)
+    }
+
+    /// Moves the entry to the front of the order, returning `false` if the key is missing.
+    pub fn move_to_front<Q>(&mut self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + PartialEq + ?Sized,
+    {
+        match self.find(key) {
+            Some(node_index) => {
+                self.unlink(node_index);
+                self.link_front(node_index);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Moves the entry to the back of the order, returning `false` if the key is missing.
+    pub fn move_to_back<Q>(&mut self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + PartialEq + ?Sized,
+    {
+        match self.find(key) {
+            Some(node_index) => {
+                self.unlink(node_index);
+                self.link_back(node_index);
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn clear(&mut self) {
+        for bucket in self.buckets.iter_mut() {
+            *bucket = None;
+        }
+        self.nodes.clear();
+        self.free.clear();
+        self.len = 0;
+        self.head = None;
+        self.tail = None;
+    }
+
+    /// Iterates from the front to the back of the order.
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter {
+            nodes: &self.nodes,
+            next: self.head,
+            remaining: self.len,
+        }
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &K> {
+        self.iter().map(|(key, _)| key)
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &V> {
+        self.iter().map(|(_, value)| value)
+    }
+
+    fn with_buckets(bucket_count: usize, nodes: Vec<Option<Node<K, V>>>, hash_builder: S) -> Self {
+        LinkedHashMap {
+            buckets: vec![None; bucket_count],
+            nodes,
+            free: Vec::new(),
+            len: 0,
+            head: None,
+            tail: None,
+            access_order: false,
+            hash_builder,
+        }
+    }
+
+    // The map grows once it holds more than three entries for every four buckets
+    fn capacity_of(bucket_count: usize) -> usize {
+        bucket_count / 4 * 3
+    }
+
+    fn buckets_for(capacity: usize) -> usize {
+        let mut bucket_count = DEFAULT_BUCKET_COUNT;
+        while Self::capacity_of(bucket_count) < capacity {
+            bucket_count = bucket_count.checked_mul(2).expect("capacity overflow");
+        }
+        bucket_count
+    }
+
+    fn bucket(&self, hash: u64) -> usize {
+        (hash % self.buckets.len() as u64) as usize
+    }
+
+    fn find<Q>(&self, key: &Q) -> Option<usize>
+    where
+        K: Borrow<Q>,
+        Q: Hash + PartialEq + ?Sized,
+    {
+        let hash = self.hash_builder.hash_one(key);
+        let mut current = self.buckets[self.bucket(hash)];
+        while let Some(node_index) = current {
+            let node = self.node(node_index);
+            if node.hash == hash && node.key.borrow() == key {
+                return Some(node_index);
+            }
+            current = node.chain;
+        }
+        None
+    }
+
+    fn rehash(&mut self, bucket_count: usize) {
+        self.buckets = vec![None; bucket_count];
+        for node_index in 0..self.nodes.len() {
+            if let Some(hash) = self.nodes[node_index].as_ref().map(|node| node.hash) {
+                let bucket = self.bucket(hash);
+                self.node_mut(node_index).chain = self.buckets[bucket];
+                self.buckets[bucket] = Some(node_index);
+            }
+        }
+    }
+
+    fn node(&self, node_index: usize) -> &Node<K, V> {
+        self.nodes[node_index].as_ref().unwrap()
+    }
+
+    fn node_mut(&mut self, node_index: usize) -> &mut Node<K, V> {
+        self.nodes[node_index].as_mut().unwrap()
+    }
+
+    fn touch(&mut self, node_index: usize) {
+        if self.access_order {
+            self.unlink(node_index);
+            self.link_back(node_index);
+        }
+    }
+
+    fn pop_at(&mut self, node_index: usize) -> Option<(K, V)> {
+        self.unchain(node_index);
+        self.unlink(node_index);
+        self.free.push(node_index);
+        self.len -= 1;
+        let node = self.nodes[node_index].take().unwrap();
+        Some((node.key, node.value))
+    }
+
+    // Takes the node out of its bucket's chain
+    fn unchain(&mut self, node_index: usize) {
+        let node = self.node(node_index);
+        let (bucket, chain) = (self.bucket(node.hash), node.chain);
+        match self.buckets[bucket] {
+            Some(first) if first == node_index => self.buckets[bucket] = chain,
+            mut current => {
+                while let Some(previous) = current {
+                    let previous = self.node_mut(previous);
+                    if previous.chain == Some(node_index) {
+                        previous.chain = chain;
+                        return;
+                    }
+                    current = previous.chain;
+                }
+            }
+        }
+    }
+
+    fn unlink(&mut self, node_index: usize) {
+        let node = self.node_mut(node_index);
+        let (prev, next) = (node.prev.take(), node.next.take());
+        match prev {
+            Some(prev) => self.node_mut(prev).next = next,
+            None => self.head = next,
+        }
+        match next {
+            Some(next) => self.node_mut(next).prev = prev,
+            None => self.tail = prev,
+        }
+    }
+
+    fn link_back(&mut self, node_index: usize) {
+        let tail = self.tail;
+        let node = self.node_mut(node_index);
+        node.prev = tail;
+        node.next = None;
+        match tail {
+            Some(tail) => self.node_mut(tail).next = Some(node_index),
+            None => self.head = Some(node_index),
+        }
+        self.tail = Some(node_index);
+    }
+
+    fn link_front(&mut self, node_index: usize) {
+        let head = self.head;
+        let node = self.node_mut(node_index);
+        node.prev = None;
+        node.next = head;
+        match head {
+            Some(head) => self.node_mut(head).prev = Some(node_index),
+            None => self.tail = Some(node_index),
+        }
+        self.head = Some(node_index);
+    }
+}
+
+impl<K: Hash + PartialEq + Debug, V: Debug, S: BuildHasher> Debug for LinkedHashMap<K, V, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+/// Maps are equal when they hold the same pairs in the same order.
+impl<K: Hash + PartialEq, V: PartialEq, S: BuildHasher> PartialEq for LinkedHashMap<K, V, S> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+impl<K: Hash + Eq, V: Eq, S: BuildHasher> Eq for LinkedHashMap<K, V, S> {}
+
+impl<K: Hash + PartialEq, V, S: BuildHasher + Default> FromIterator<(K, V)>
+    for LinkedHashMap<K, V, S>
+{
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut map = LinkedHashMap::with_hasher(S::default());
+        map.extend(iter);
+        map
+    }
+}
+
+impl<K: Hash + PartialEq, V, S: BuildHasher> Extend<(K, V)> for LinkedHashMap<K, V, S> {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            self.put(key, value);
+        }
+    }
+}
+
+impl<'a, K: Hash + PartialEq, V, S: BuildHasher> IntoIterator for &'a LinkedHashMap<K, V, S> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<K: Hash + PartialEq, V, S: BuildHasher> IntoIterator for LinkedHashMap<K, V, S> {
+    type Item = (K, V);
+    type IntoIter = IntoIter<K, V, S>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter(self)
+    }
+}
+
+pub struct Iter<'a, K, V> {
+    nodes: &'a [Option<Node<K, V>>],
+    next: Option<usize>,
+    remaining: usize,
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        let node = self.nodes[self.next?].as_ref().unwrap();
+        self.next = node.next;
+        self.remaining -= 1;
+        Some((&node.key, &node.value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<'a, K, V> ExactSizeIterator for Iter<'a, K, V> {}
+
+pub struct IntoIter<K: Hash + PartialEq, V, S>(LinkedHashMap<K, V, S>);
+
+impl<K: Hash + PartialEq, V, S: BuildHasher> Iterator for IntoIter<K, V, S> {
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.pop_front()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.0.len(), Some(self.0.len()))
+    }
+}
+
+impl<K: Hash + PartialEq, V, S: BuildHasher> DoubleEndedIterator for IntoIter<K, V, S> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.pop_back()
+    }
+}
+
+impl<K: Hash + PartialEq, V, S: BuildHasher> ExactSizeIterator for IntoIter<K, V, S> {}
+
+#[cfg(test)]
+mod tests {
+    use crate::linked_hashmap::LinkedHashMap;
+    use std::borrow::Borrow;
+    use std::hash::{BuildHasherDefault, Hasher};
+
+    fn keys<V>(map: &LinkedHashMap<&'static str, V>) -> Vec<&'static str> {
+        map.keys().cloned().collect()
+    }
+
+    #[test]
+    fn insertion_order() {
+        let mut map = LinkedHashMap::new();
+        map.put("zeta", 1);
+        map.put("alpha", 2);
+        map.put("mu", 3);
+        map.put("beta", 4);
+        assert_eq!(keys(&map), vec!["zeta", "alpha", "mu", "beta"]);
+        // Updating a key keeps its place
+        map.put("alpha", 20);
+        assert_eq!(map.get("alpha"), Some(&20));
+        assert_eq!(keys(&map), vec!["zeta", "alpha", "mu", "beta"]);
+        assert_eq!(map.len(), 4);
+    }
+
+    #[test]
+    fn remove_and_reuse() {
+        let mut map: LinkedHashMap<&str, i32> =
+            vec![("a", 1), ("b", 2), ("c", 3)].into_iter().collect();
+        assert_eq!(map.remove("b"), Some(2));
+        assert_eq!(map.remove("b"), None);
+        assert_eq!(keys(&map), vec!["a", "c"]);
+        map.put("d", 4);
+        assert_eq!(keys(&map), vec!["a", "c", "d"]);
+        // The freed slot is reused
+        assert_eq!(map.nodes.len(), 3);
+        assert_eq!(map.remove_entry("a"), Some(("a", 1)));
+        assert_eq!(map.remove("d"), Some(4));
+        assert_eq!(map.remove("c"), Some(3));
+        assert!(map.is_empty());
+        assert_eq!(map.front(), None);
+        assert_eq!(map.back(), None);
+    }
+
+    #[test]
+    fn move_to_front_and_back() {
+        let mut map: LinkedHashMap<&str, i32> =
+            vec![("a", 1), ("b", 2), ("c", 3)].into_iter().collect();
+        assert!(map.move_to_front("c"));
+        assert_eq!(keys(&map), vec!["c", "a", "b"]);
+        assert!(map.move_to_back("c"));
+        assert_eq!(keys(&map), vec!["a", "b", "c"]);
+        assert!(map.move_to_back("a"));
+        assert_eq!(keys(&map), vec!["b", "c", "a"]);
+        assert!(map.move_to_front("b"));
+        assert_eq!(keys(&map), vec!["b", "c", "a"]);
+        assert!(!map.move_to_front("z"));
+        assert_eq!(map.front(), Some((&"b", &2)));
+        assert_eq!(map.back(), Some((&"a", &1)));
+    }
+
+    #[test]
+    fn pop_front_and_back() {
+        let mut map: LinkedHashMap<&str, i32> =
+            vec![("a", 1), ("b", 2), ("c", 3)].into_iter().collect();
+        assert_eq!(map.pop_front(), Some(("a", 1)));
+        assert_eq!(map.pop_back(), Some(("c", 3)));
+        assert_eq!(map.len(), 1);
+        assert!(!map.contains_key("a"));
+        assert_eq!(map.pop_back(), Some(("b", 2)));
+        assert_eq!(map.pop_front(), None);
+        assert_eq!(map.pop_back(), None);
+    }
+
+    #[test]
+    fn access_order() {
+        let mut map = LinkedHashMap::with_access_order();
+        assert!(map.is_access_order());
+        map.put("a", 1);
+        map.put("b", 2);
+        map.put("c", 3);
+        assert_eq!(map.get("a"), Some(&1));
+        assert_eq!(keys(&map), vec!["b", "c", "a"]);
+        *map.get_mut("b").unwrap() += 10;
+        assert_eq!(keys(&map), vec!["c", "a", "b"]);
+        map.put("c", 30);
+        assert_eq!(keys(&map), vec!["a", "b", "c"]);
+        // Peeking and membership checks are not accesses
+        assert_eq!(map.peek("a"), Some(&1));
+        assert!(map.contains_key("b"));
+        assert_eq!(keys(&map), vec!["a", "b", "c"]);
+        assert_eq!(map.get("missing"), None);
+    }
+
+    #[test]
+    fn peek_keeps_iteration_order() {
+        let mut map = LinkedHashMap::with_access_order();
+        map.put("a", 1);
+        map.put("b", 2);
+        map.put("c", 3);
+        let peeked: Vec<_> = map.keys().map(|key| map.peek(key)).collect();
+        assert_eq!(peeked, vec![Some(&1), Some(&2), Some(&3)]);
+        assert_eq!(keys(&map), vec!["a", "b", "c"]);
+    }
+
+    #[test]
+    fn is_send_and_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<LinkedHashMap<String, i32>>();
+    }
+
+    #[test]
+    fn insertion_order_ignores_access() {
+        let mut map = LinkedHashMap::new();
+        map.put("a", 1);
+        map.put("b", 2);
+        map.get("a");
+        *map.get_mut("a").unwrap() += 1;
+        assert_eq!(keys(&map), vec!["a", "b"]);
+    }
+
+    #[test]
+    fn iteration() {
+        let map: LinkedHashMap<i32, i32> = (0..10).rev().map(|i| (i, i * i)).collect();
+        let iter = map.iter();
+        assert_eq!(iter.len(), 10);
+        assert_eq!(
+            iter.map(|(key, _)| *key).collect::<Vec<_>>(),
+            (0..10).rev().collect::<Vec<_>>()
+        );
+        assert_eq!(map.values().cloned().sum::<i32>(), 285);
+        let mut borrowed = 0;
+        for (key, value) in &map {
+            assert_eq!(key * key, *value);
+            borrowed += 1;
+        }
+        assert_eq!(borrowed, 10);
+        let mut owned = map.into_iter();
+        assert_eq!(owned.next(), Some((9, 81)));
+        assert_eq!(owned.next_back(), Some((0, 0)));
+        assert_eq!(owned.len(), 8);
+    }
+
+    #[test]
+    fn clear() {
+        let mut map: LinkedHashMap<i32, i32> = (0..5).map(|i| (i, i)).collect();
+        map.clear();
+        assert!(map.is_empty());
+        assert_eq!(map.iter().next(), None);
+        map.put(7, 7);
+        assert_eq!(map.front(), Some((&7, &7)));
+    }
+
+    #[test]
+    fn clone_eq_and_debug() {
+        let map: LinkedHashMap<&str, i32> = vec![("a", 1), ("b", 2)].into_iter().collect();
+        let mut copy = map.clone();
+        assert_eq!(map, copy);
+        assert!(copy.move_to_front("b"));
+        // The order is part of the contents
+        assert_ne!(map, copy);
+        assert_eq!(format!("{:?}", map), r#"{"a": 1, "b": 2}"#);
+        assert_eq!(format!("{:?}", copy), r#"{"b": 2, "a": 1}"#);
+    }
+
+    #[test]
+    fn keys_need_not_be_clone() {
+        #[derive(Hash, PartialEq, Debug)]
+        struct Key(String);
+
+        impl Borrow<str> for Key {
+            fn borrow(&self) -> &str {
+                &self.0
+            }
+        }
+
+        let mut map = LinkedHashMap::new();
+        for i in 0..1000 {
+            map.put(Key(i.to_string()), i);
+        }
+        assert_eq!(map.len(), 1000);
+        assert!(map.buckets.len() > 1000);
+        assert!((0..1000).all(|i| map.peek(i.to_string().as_str()) == Some(&i)));
+        assert_eq!(map.remove_entry("500"), Some((Key("500".to_string()), 500)));
+        assert_eq!(map.front(), Some((&Key("0".to_string()), &0)));
+    }
+
+    #[derive(Default)]
+    struct ConstantHasher;
+
+    impl Hasher for ConstantHasher {
+        fn finish(&self) -> u64 {
+            7
+        }
+
+        fn write(&mut self, _bytes: &[u8]) {}
+    }
+
+    #[test]
+    fn colliding_keys() {
+        let mut map: LinkedHashMap<i32, i32, BuildHasherDefault<ConstantHasher>> =
+            (0..40).map(|i| (i, i * 10)).collect();
+        assert!((0..40).all(|i| map.peek(&i) == Some(&(i * 10))));
+        // Removes from the start, the middle and the end of the shared chain
+        assert_eq!(map.remove(&39), Some(390));
+        assert_eq!(map.remove(&20), Some(200));
+        assert_eq!(map.remove(&0), Some(0));
+        assert_eq!(map.remove(&20), None);
+        assert_eq!(map.len(), 37);
+        assert!((1..39)
+            .filter(|&i| i != 20)
+            .all(|i| map.peek(&i) == Some(&(i * 10))));
+        map.put(20, 2);
+        assert_eq!(map.back(), Some((&20, &2)));
+    }
+}
@@ -1,24 +1,33 @@
-use std::fmt::Debug;
+use crate::error::DestructError;
+use std::cmp::Ordering;
+use std::fmt::{self, Debug};
+use std::hash::{Hash, Hasher};
+use std::iter::FromIterator;
 
 #[derive(PartialOrd, PartialEq, Debug)]
-struct LinkedListNode<T: PartialEq> {
+struct LinkedListNode<T> {
     data: T,
     next_node: Option<Box<LinkedListNode<T>>>,
 }
 
-#[derive(PartialEq, Debug)]
-pub struct LinkedList<T: PartialEq> {
+pub struct LinkedList<T> {
     head: Option<Box<LinkedListNode<T>>>,
     len: usize,
 }
 
-impl<T: PartialEq> LinkedListNode<T> {
+impl<T> LinkedListNode<T> {
     pub fn has_next(&self) -> bool {
         self.next_node.is_some()
     }
 }
 
-impl<T: PartialEq> LinkedList<T> {
+impl<T> Default for LinkedList<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> LinkedList<T> {
     pub fn get_head(&self) -> Option<&T> {
         self.head.as_ref().map(|node| &node.data)
     }
@@ -37,7 +46,7 @@ impl<T: PartialEq> LinkedList<T> {
 
     pub fn get_tail(&self) -> Option<&T> {
         // Empty LinkedList has no head
-        return if let Some(ref head) = self.head {
+        if let Some(ref head) = self.head {
             let mut tail = head;
             // Loop until the last node is found
             while let Some(ref next) = tail.next_node {
@@ -46,11 +55,11 @@ impl<T: PartialEq> LinkedList<T> {
             Some(&tail.data)
         } else {
             None
-        };
+        }
     }
 
     pub fn get_tail_mut(&mut self) -> Option<&mut T> {
-        return if let Some(ref mut head) = self.head {
+        if let Some(ref mut head) = self.head {
             let mut tail = head;
             while tail.has_next() {
                 tail = tail.next_node.as_mut().unwrap();
@@ -58,7 +67,7 @@ impl<T: PartialEq> LinkedList<T> {
             Some(&mut tail.data)
         } else {
             None
-        };
+        }
     }
 
     pub fn new() -> Self {
@@ -92,22 +101,477 @@ impl<T: PartialEq> LinkedList<T> {
         self.len += 1;
     }
 
+    /// Inserts `value` so that it ends up at `index`. An index equal to `len` appends.
+    pub fn insert_at(&mut self, index: usize, value: T) -> Result<(), DestructError> {
+        if index > self.len {
+            return Err(self.out_of_bounds(index));
+        }
+        let mut cursor = self.cursor_front_mut();
+        for _ in 0..index {
+            cursor.move_next();
+        }
+        cursor.insert_before(value);
+        Ok(())
+    }
+
+    pub fn remove_at(&mut self, index: usize) -> Result<T, DestructError> {
+        self.check_index(index)?;
+        let mut cursor = self.cursor_front_mut();
+        for _ in 0..index {
+            cursor.move_next();
+        }
+        Ok(cursor.remove_current().unwrap())
+    }
+
+    pub fn get(&self, index: usize) -> Result<&T, DestructError> {
+        self.check_index(index)?;
+        Ok(self.iter().nth(index).unwrap())
+    }
+
+    pub fn get_mut(&mut self, index: usize) -> Result<&mut T, DestructError> {
+        self.check_index(index)?;
+        Ok(self.iter_mut().nth(index).unwrap())
+    }
+
+    /// Swaps the elements at `first` and `second` without moving any nodes.
+    pub fn swap(&mut self, first: usize, second: usize) -> Result<(), DestructError> {
+        self.check_index(first)?;
+        self.check_index(second)?;
+        let (low, high) = if first < second {
+            (first, second)
+        } else {
+            (second, first)
+        };
+        if low == high {
+            return Ok(());
+        }
+        let mut low_node = self.head.as_deref_mut().unwrap();
+        for _ in 0..low {
+            low_node = low_node.next_node.as_deref_mut().unwrap();
+        }
+        let LinkedListNode { data, next_node } = low_node;
+        let mut high_node = next_node.as_deref_mut().unwrap();
+        for _ in low + 1..high {
+            high_node = high_node.next_node.as_deref_mut().unwrap();
+        }
+        std::mem::swap(data, &mut high_node.data);
+        Ok(())
+    }
+
+    /// Splits the list in two at `index`, returning the elements from `index` onwards.
+    pub fn split_off(&mut self, index: usize) -> Result<LinkedList<T>, DestructError> {
+        if index > self.len {
+            return Err(self.out_of_bounds(index));
+        }
+        let head = self.link_at(index).take();
+        let len = self.len - index;
+        self.len = index;
+        Ok(LinkedList { head, len })
+    }
+
+    /// Moves every element of `other` to the end of this list, leaving `other` empty.
+    pub fn append(&mut self, other: &mut LinkedList<T>) {
+        let other = std::mem::take(other);
+        self.splice(self.len, other).unwrap();
+    }
+
+    /// Inserts all elements of `other` so that the first of them ends up at `index`.
+    pub fn splice(&mut self, index: usize, mut other: LinkedList<T>) -> Result<(), DestructError> {
+        if index > self.len {
+            return Err(self.out_of_bounds(index));
+        }
+        let inserted = std::mem::replace(&mut other.len, 0);
+        let head = other.head.take();
+        let link = self.link_at(index);
+        let rest = std::mem::replace(link, head);
+        let mut tail = link;
+        while tail.is_some() {
+            tail = &mut tail.as_mut().unwrap().next_node;
+        }
+        *tail = rest;
+        self.len += inserted;
+        Ok(())
+    }
+
+    /// Drops every element after the first `len` ones.
+    pub fn truncate(&mut self, len: usize) {
+        if len < self.len {
+            // The detached tail is dropped through `LinkedList`, one node at a time
+            drop(self.split_off(len));
+        }
+    }
+
+    pub fn retain<F>(&mut self, mut keep: F)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        self.retain_mut(|element| keep(element));
+    }
+
+    pub fn retain_mut<F>(&mut self, mut keep: F)
+    where
+        F: FnMut(&mut T) -> bool,
+    {
+        let mut cursor = self.cursor_front_mut();
+        while let Some(element) = cursor.current() {
+            if keep(element) {
+                cursor.move_next();
+            } else {
+                cursor.remove_current();
+            }
+        }
+    }
+
+    /// Removes consecutive repeated elements, keeping the first of each run.
+    pub fn dedup(&mut self)
+    where
+        T: PartialEq,
+    {
+        self.dedup_by(|next, previous| next == previous);
+    }
+
+    pub fn dedup_by_key<K, F>(&mut self, mut key: F)
+    where
+        K: PartialEq,
+        F: FnMut(&mut T) -> K,
+    {
+        self.dedup_by(|next, previous| key(next) == key(previous));
+    }
+
+    /// Removes every element for which `same_bucket(element, previous)` is true, where
+    /// `previous` is the last element that was kept.
+    pub fn dedup_by<F>(&mut self, mut same_bucket: F)
+    where
+        F: FnMut(&mut T, &mut T) -> bool,
+    {
+        let LinkedList { head, len } = self;
+        let mut node = match head.as_deref_mut() {
+            Some(node) => node,
+            None => return,
+        };
+        loop {
+            let LinkedListNode { data, next_node } = node;
+            let duplicate = match next_node.as_deref_mut() {
+                Some(next) => same_bucket(&mut next.data, data),
+                None => break,
+            };
+            if duplicate {
+                let removed = node.next_node.take().unwrap();
+                node.next_node = removed.next_node;
+                *len -= 1;
+            } else {
+                node = node.next_node.as_deref_mut().unwrap();
+            }
+        }
+    }
+
+    /// Lazily removes and yields the elements matching `predicate`. Elements that were
+    /// not reached yet stay in the list if the iterator is dropped early.
+    pub fn drain_filter<F>(&mut self, predicate: F) -> DrainFilter<'_, T, F>
+    where
+        F: FnMut(&mut T) -> bool,
+    {
+        DrainFilter {
+            cursor: self.cursor_front_mut(),
+            predicate,
+        }
+    }
+
+    // Walks `index` links from the head. Callers make sure that `index <= len`.
+    fn link_at(&mut self, index: usize) -> &mut Option<Box<LinkedListNode<T>>> {
+        let mut link = &mut self.head;
+        for _ in 0..index {
+            link = &mut link.as_mut().unwrap().next_node;
+        }
+        link
+    }
+
+    fn check_index(&self, index: usize) -> Result<(), DestructError> {
+        if index < self.len {
+            Ok(())
+        } else {
+            Err(self.out_of_bounds(index))
+        }
+    }
+
+    fn out_of_bounds(&self, index: usize) -> DestructError {
+        DestructError::IndexOutOfBounds {
+            index,
+            len: self.len,
+        }
+    }
+
+    pub fn delete_match<F>(&mut self, predicate: F) -> Option<T>
+    where
+        F: Fn(&T) -> bool,
+    {
+        if let Some(prev) = self.get_prev_node_match(&predicate) {
+            if let Some(node_to_delete) = prev.next_node.take() {
+                prev.next_node = node_to_delete.next_node;
+                self.len -= 1;
+                return Some(node_to_delete.data);
+            }
+        } else {
+            if predicate(&self.head.as_ref()?.data) {
+                let head = self.head.take()?;
+                self.head = head.next_node;
+                self.len -= 1;
+                return Some(head.data);
+            }
+        }
+        None
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        self.head.take().map(|head| {
+            self.head = head.next_node;
+            self.len -= 1;
+            head.data
+        })
+    }
+
+    pub fn peek(&self) -> Option<&T> {
+        self.head.as_ref().map(|head| &head.data)
+    }
+
+    pub fn contains_match<F>(&self, predicate: &F) -> bool
+    where
+        F: Fn(&T) -> bool,
+    {
+        if let Some(mut node) = self.head.as_ref() {
+            while !predicate(&node.data) {
+                if let Some(ref next) = node.next_node {
+                    node = next;
+                } else {
+                    return false;
+                }
+            }
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Walks the list once, returning either the first element matching `predicate`
+    /// or the empty link after the tail where a new element can be attached.
+    pub(crate) fn find_mut_or_vacant<F>(
+        &mut self,
+        predicate: F,
+    ) -> Result<&mut T, VacantNode<'_, T>>
+    where
+        F: Fn(&T) -> bool,
+    {
+        let LinkedList { head, len } = self;
+        let mut link = head;
+        loop {
+            if link.is_none() {
+                return Err(VacantNode { link, len });
+            }
+            let node = link.as_mut().unwrap();
+            if predicate(&node.data) {
+                return Ok(&mut node.data);
+            }
+            link = &mut node.next_node;
+        }
+    }
+
+    fn get_prev_node_match<F>(&mut self, predicate: &F) -> Option<&mut Box<LinkedListNode<T>>>
+    where
+        F: Fn(&T) -> bool,
+    {
+        if let Some(ref mut head) = self.head {
+            let mut node = head;
+            while let Some(ref next) = node.next_node {
+                if predicate(&next.data) {
+                    return Some(node);
+                } else {
+                    node = node.next_node.as_mut().unwrap();
+                }
+            }
+            None
+        } else {
+            None
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            next: self.head.as_deref(),
+        }
+    }
+
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut {
+            next: self.head.as_deref_mut(),
+        }
+    }
+
+    pub fn reverse(&mut self) {
+        let mut reversed = None;
+        let mut link = self.head.take();
+        while let Some(mut node) = link {
+            link = std::mem::replace(&mut node.next_node, reversed);
+            reversed = Some(node);
+        }
+        self.head = reversed;
+    }
+
+    pub fn sort(&mut self)
+    where
+        T: Ord,
+    {
+        self.sort_by(|a, b| a.cmp(b));
+    }
+
+    /// Stable merge sort that relinks the nodes in place, in O(n log n).
+    pub fn sort_by<F>(&mut self, mut compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        let head = self.head.take();
+        self.head = Self::merge_sort(head, self.len, &mut compare);
+    }
+
+    pub fn sort_by_key<K, F>(&mut self, mut key: F)
+    where
+        K: Ord,
+        F: FnMut(&T) -> K,
+    {
+        self.sort_by(|a, b| key(a).cmp(&key(b)));
+    }
+
+    /// Same as `sort_by`. Merge sort is stable anyway, so this exists for parity with
+    /// slices and gives no extra speed.
+    pub fn sort_unstable_by<F>(&mut self, compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        self.sort_by(compare);
+    }
+
+    pub fn is_sorted(&self) -> bool
+    where
+        T: PartialOrd,
+    {
+        self.iter().zip(self.iter().skip(1)).all(|(a, b)| a <= b)
+    }
+
+    /// Inserts `value` after every element that is less than or equal to it, which
+    /// keeps a sorted list sorted.
+    pub fn insert_sorted(&mut self, value: T)
+    where
+        T: Ord,
+    {
+        let mut cursor = self.cursor_front_mut();
+        while cursor.current().is_some_and(|current| *current <= value) {
+            cursor.move_next();
+        }
+        cursor.insert_before(value);
+    }
+
+    /// Merges the sorted `other` into this sorted list. Equal elements of `self` come first.
+    pub fn merge(&mut self, other: LinkedList<T>)
+    where
+        T: Ord,
+    {
+        self.merge_by(other, |a, b| a.cmp(b));
+    }
+
+    pub fn merge_by<F>(&mut self, mut other: LinkedList<T>, mut compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        let head = self.head.take();
+        self.head = Self::merge_nodes(head, other.head.take(), &mut compare);
+        self.len += other.len;
+        other.len = 0;
+    }
+
+    fn merge_sort<F>(
+        mut head: Option<Box<LinkedListNode<T>>>,
+        len: usize,
+        compare: &mut F,
+    ) -> Option<Box<LinkedListNode<T>>>
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        if len <= 1 {
+            return head;
+        }
+        let middle = len / 2;
+        let mut node = head.as_mut().unwrap();
+        for _ in 1..middle {
+            node = node.next_node.as_mut().unwrap();
+        }
+        let right = node.next_node.take();
+        let left = Self::merge_sort(head, middle, compare);
+        let right = Self::merge_sort(right, len - middle, compare);
+        Self::merge_nodes(left, right, compare)
+    }
+
+    // Iterative so that merging long runs doesn't recurse once per node
+    fn merge_nodes<F>(
+        mut left: Option<Box<LinkedListNode<T>>>,
+        mut right: Option<Box<LinkedListNode<T>>>,
+        compare: &mut F,
+    ) -> Option<Box<LinkedListNode<T>>>
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        let mut merged = None;
+        let mut tail = &mut merged;
+        while let (Some(l), Some(r)) = (left.as_ref(), right.as_ref()) {
+            let take_left = compare(&r.data, &l.data) != Ordering::Less;
+            let source = if take_left { &mut left } else { &mut right };
+            let mut node = source.take().unwrap();
+            *source = node.next_node.take();
+            tail = &mut tail.insert(node).next_node;
+        }
+        *tail = if left.is_some() { left } else { right };
+        merged
+    }
+
+    /// Returns a cursor positioned on the head, or past the end if the list is empty.
+    pub fn cursor_front_mut(&mut self) -> CursorMut<'_, T> {
+        let LinkedList { head, len } = self;
+        CursorMut {
+            link: Some(head),
+            len,
+            index: 0,
+        }
+    }
+}
+
+impl<T: PartialEq> LinkedList<T> {
     pub fn add_after(&mut self, value: T, after: &T)
     where
         T: Debug,
     {
-        let after_node = self
-            .find_mut(after)
-            .expect(format!("Cannot find LinkedListNode with value: {:?}", after).as_str());
+        if self.try_add_after(value, after).is_err() {
+            panic!("Cannot find LinkedListNode with value: {:?}", after);
+        }
+    }
+
+    pub fn try_add_after(&mut self, value: T, after: &T) -> Result<(), DestructError> {
+        let after_node = self.find_mut(after).ok_or(DestructError::ElementNotFound)?;
         after_node.next_node = Some(Box::from(LinkedListNode {
             data: value,
             next_node: after_node.next_node.take(),
         }));
         self.len += 1;
+        Ok(())
     }
 
     fn find_mut(&mut self, value: &T) -> Option<&mut LinkedListNode<T>> {
-        return if let Some(ref mut head) = self.head {
+        if let Some(ref mut head) = self.head {
             let mut node = head;
             while &node.data != value {
                 if let Some(ref mut next) = node.next_node {
@@ -119,7 +583,7 @@ impl<T: PartialEq> LinkedList<T> {
             Some(node)
         } else {
             None
-        };
+        }
     }
 
     pub fn delete(&mut self, value: &T) {
@@ -135,172 +599,336 @@ impl<T: PartialEq> LinkedList<T> {
         self.len -= 1;
     }
 
-    pub fn delete_match<F>(&mut self, predicate: F) -> Option<T>
-    where
-        F: Fn(&T) -> bool,
-    {
-        if let Some(prev) = self.get_prev_node_match(&predicate) {
-            if let Some(node_to_delete) = prev.next_node.take() {
-                prev.next_node = node_to_delete.next_node;
-                self.len -= 1;
-                return Some(node_to_delete.data);
+    pub fn contains(&self, value: &T) -> bool {
+        if let Some(mut node) = self.head.as_ref() {
+            while &node.data != value {
+                if let Some(ref next) = node.next_node {
+                    node = next;
+                } else {
+                    return false;
+                }
             }
+            true
         } else {
-            if predicate(&self.head.as_ref()?.data) {
-                let head = self.head.take()?;
-                self.head = head.next_node;
-                self.len -= 1;
-                return Some(head.data);
+            false
+        }
+    }
+
+    fn get_prev_node(&mut self, value: &T) -> Option<&mut Box<LinkedListNode<T>>> {
+        if let Some(ref mut head) = self.head {
+            let mut node = head;
+            while let Some(ref next) = node.next_node {
+                if &next.data == value {
+                    return Some(node);
+                } else {
+                    node = node.next_node.as_mut().unwrap();
+                }
             }
+            None
+        } else {
+            None
+        }
+    }
+}
+
+// Detaches each `next_node` before its box is dropped, keeping the drop iterative
+impl<T> Drop for LinkedList<T> {
+    fn drop(&mut self) {
+        let mut link = self.head.take();
+        while let Some(mut node) = link {
+            link = node.next_node.take();
+        }
+    }
+}
+
+impl<T> From<Vec<T>> for LinkedList<T> {
+    fn from(vec: Vec<T>) -> Self {
+        vec.into_iter().collect()
+    }
+}
+
+impl<T> FromIterator<T> for LinkedList<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut list = LinkedList::new();
+        list.extend(iter);
+        list
+    }
+}
+
+// Finds the tail once and keeps appending after it, instead of walking the list per element
+impl<T> Extend<T> for LinkedList<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let mut added = 0;
+        let mut link = self.link_at(self.len);
+        for element in iter {
+            let node = link.insert(Box::from(LinkedListNode {
+                data: element,
+                next_node: None,
+            }));
+            link = &mut node.next_node;
+            added += 1;
+        }
+        self.len += added;
+    }
+}
+
+impl<T: Clone> Clone for LinkedList<T> {
+    fn clone(&self) -> Self {
+        self.iter().cloned().collect()
+    }
+}
+
+impl<T: Debug> Debug for LinkedList<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+// Compares element by element rather than through the nodes, so long lists don't recurse
+impl<T: PartialEq> PartialEq for LinkedList<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Eq> Eq for LinkedList<T> {}
+
+impl<T: PartialOrd> PartialOrd for LinkedList<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.iter().partial_cmp(other.iter())
+    }
+}
+
+impl<T: Ord> Ord for LinkedList<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.iter().cmp(other.iter())
+    }
+}
+
+impl<T: Hash> Hash for LinkedList<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.len.hash(state);
+        for element in self.iter() {
+            element.hash(state);
+        }
+    }
+}
+
+pub(crate) struct VacantNode<'a, T> {
+    link: &'a mut Option<Box<LinkedListNode<T>>>,
+    len: &'a mut usize,
+}
+
+impl<'a, T> VacantNode<'a, T> {
+    pub(crate) fn insert(self, value: T) -> &'a mut T {
+        *self.len += 1;
+        let node = self.link.insert(Box::from(LinkedListNode {
+            data: value,
+            next_node: None,
+        }));
+        &mut node.data
+    }
+}
+
+/// Cursor over a `LinkedList` that can edit the list while walking it.
+///
+/// The cursor sits either on an element or past the end of the list, where there is no
+/// current element. Since the list is singly linked, it only moves forward.
+pub struct CursorMut<'a, T> {
+    // Link that owns the current node. Only `None` while a method is moving the cursor.
+    link: Option<&'a mut Option<Box<LinkedListNode<T>>>>,
+    len: &'a mut usize,
+    index: usize,
+}
+
+impl<'a, T> CursorMut<'a, T> {
+    /// Position of the current element, or `None` past the end.
+    pub fn index(&self) -> Option<usize> {
+        if self.current_link().is_some() {
+            Some(self.index)
+        } else {
+            None
         }
-        None
     }
 
-    pub fn pop(&mut self) -> Option<T> {
-        self.head.take().map(|head| {
-            self.head = head.next_node;
-            self.len -= 1;
-            return head.data;
-        })
+    pub fn current(&mut self) -> Option<&mut T> {
+        self.current_link_mut().as_mut().map(|node| &mut node.data)
     }
 
-    pub fn peek(&self) -> Option<&T> {
-        self.head.as_ref().map(|head| &head.data)
+    pub fn peek_next(&mut self) -> Option<&mut T> {
+        self.current_link_mut()
+            .as_mut()?
+            .next_node
+            .as_mut()
+            .map(|node| &mut node.data)
     }
 
-    pub fn contains(&self, value: &T) -> bool {
-        if let Some(mut node) = self.head.as_ref() {
-            while &node.data != value {
-                if let Some(ref next) = node.next_node {
-                    node = next;
-                } else {
-                    return false;
-                }
-            }
-            return true;
+    /// Moves to the next element. Does nothing once the cursor is past the end.
+    pub fn move_next(&mut self) {
+        let link = self.link.take().unwrap();
+        if link.is_some() {
+            self.link = Some(&mut link.as_mut().unwrap().next_node);
+            self.index += 1;
         } else {
-            false
+            self.link = Some(link);
         }
     }
 
-    pub fn contains_match<F>(&self, predicate: &F) -> bool
-    where
-        F: Fn(&T) -> bool,
-    {
-        return if let Some(mut node) = self.head.as_ref() {
-            while !predicate(&node.data) {
-                if let Some(ref next) = node.next_node {
-                    node = next;
-                } else {
-                    return false;
-                }
-            }
-            true
-        } else {
-            false
-        };
+    /// Inserts `value` before the current element and keeps the cursor on the current
+    /// element. Past the end, this appends `value` to the list.
+    pub fn insert_before(&mut self, value: T) {
+        let link = self.link.take().unwrap();
+        let next_node = link.take();
+        let node = link.insert(Box::from(LinkedListNode {
+            data: value,
+            next_node,
+        }));
+        self.link = Some(&mut node.next_node);
+        self.index += 1;
+        *self.len += 1;
     }
 
-    fn get_prev_node(&mut self, value: &T) -> Option<&mut Box<LinkedListNode<T>>> {
-        return if let Some(ref mut head) = self.head {
-            let mut node = head;
-            while let Some(ref next) = node.next_node {
-                if &next.data == value {
-                    return Some(node);
-                } else {
-                    node = node.next_node.as_mut().unwrap();
-                }
+    /// Inserts `value` after the current element. Past the end, `value` is appended and
+    /// becomes the current element.
+    pub fn insert_after(&mut self, value: T) {
+        let link = self.current_link_mut();
+        match link {
+            Some(node) => {
+                node.next_node = Some(Box::from(LinkedListNode {
+                    data: value,
+                    next_node: node.next_node.take(),
+                }));
             }
-            None
-        } else {
-            None
-        };
+            None => {
+                *link = Some(Box::from(LinkedListNode {
+                    data: value,
+                    next_node: None,
+                }));
+            }
+        }
+        *self.len += 1;
     }
 
-    fn get_prev_node_match<F>(&mut self, predicate: &F) -> Option<&mut Box<LinkedListNode<T>>>
-    where
-        F: Fn(&T) -> bool,
-    {
-        return if let Some(ref mut head) = self.head {
-            let mut node = head;
-            while let Some(ref next) = node.next_node {
-                if predicate(&next.data) {
-                    return Some(node);
-                } else {
-                    node = node.next_node.as_mut().unwrap();
-                }
-            }
-            None
+    /// Removes and returns the current element, moving the cursor to the one after it.
+    pub fn remove_current(&mut self) -> Option<T> {
+        let link = self.current_link_mut();
+        let node = link.take()?;
+        *link = node.next_node;
+        *self.len -= 1;
+        Some(node.data)
+    }
+
+    /// Detaches every element after the current one into a new list. Past the end, the
+    /// returned list is empty.
+    pub fn split_after(&mut self) -> LinkedList<T> {
+        let head = match self.current_link_mut() {
+            Some(node) => node.next_node.take(),
+            None => None,
+        };
+        let len = if head.is_some() {
+            *self.len - self.index - 1
         } else {
-            None
+            0
         };
+        *self.len -= len;
+        LinkedList { head, len }
     }
 
-    pub fn len(&self) -> usize {
-        self.len
+    fn current_link(&self) -> &Option<Box<LinkedListNode<T>>> {
+        self.link.as_ref().unwrap()
     }
 
-    pub fn into_iter(self) -> IntoIter<T> {
-        IntoIter(self)
+    fn current_link_mut(&mut self) -> &mut Option<Box<LinkedListNode<T>>> {
+        self.link.as_mut().unwrap()
     }
+}
 
-    pub fn iter(&self) -> Iter<T> {
-        Iter {
-            next: self.head.as_ref().map(|node| &**node),
+pub struct DrainFilter<'a, T, F>
+where
+    F: FnMut(&mut T) -> bool,
+{
+    cursor: CursorMut<'a, T>,
+    predicate: F,
+}
+
+impl<'a, T, F> Iterator for DrainFilter<'a, T, F>
+where
+    F: FnMut(&mut T) -> bool,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let element = self.cursor.current()?;
+            if (self.predicate)(element) {
+                return self.cursor.remove_current();
+            }
+            self.cursor.move_next();
         }
     }
+}
 
-    pub fn iter_mut(&mut self) -> IterMut<T> {
-        IterMut {
-            next: self.head.as_mut().map(|node| &mut **node),
-        }
+pub struct IntoIter<T>(LinkedList<T>);
+
+impl<T> IntoIterator for LinkedList<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter(self)
     }
 }
 
-impl<T: PartialEq> From<Vec<T>> for LinkedList<T> {
-    fn from(vec: Vec<T>) -> Self {
-        let mut list = LinkedList::new();
-        for element in vec {
-            list.add(element);
-        }
-        list
+impl<'a, T> IntoIterator for &'a LinkedList<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
     }
 }
 
-pub struct IntoIter<T: PartialEq>(LinkedList<T>);
+impl<'a, T> IntoIterator for &'a mut LinkedList<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
 
-impl<T: PartialEq> Iterator for IntoIter<T> {
+impl<T> Iterator for IntoIter<T> {
     type Item = T;
     fn next(&mut self) -> Option<Self::Item> {
         self.0.pop()
     }
 }
 
-pub struct Iter<'a, T: PartialEq> {
+pub struct Iter<'a, T> {
     next: Option<&'a LinkedListNode<T>>,
 }
 
-impl<'a, T: PartialEq> Iterator for Iter<'a, T> {
+impl<'a, T> Iterator for Iter<'a, T> {
     type Item = &'a T;
 
     fn next(&mut self) -> Option<Self::Item> {
         self.next.map(|node| {
-            self.next = node.next_node.as_ref().map(|node| &**node);
+            self.next = node.next_node.as_deref();
             &node.data
         })
     }
 }
 
-pub struct IterMut<'a, T: PartialEq> {
+pub struct IterMut<'a, T> {
     next: Option<&'a mut LinkedListNode<T>>,
 }
 
-impl<'a, T: PartialEq> Iterator for IterMut<'a, T> {
+impl<'a, T> Iterator for IterMut<'a, T> {
     type Item = &'a mut T;
 
     fn next(&mut self) -> Option<Self::Item> {
         self.next.take().map(|node| {
-            self.next = node.next_node.as_mut().map(|node| &mut **node);
+            self.next = node.next_node.as_deref_mut();
             &mut node.data
         })
     }
@@ -308,7 +936,9 @@ impl<'a, T: PartialEq> Iterator for IterMut<'a, T> {
 
 #[cfg(test)]
 mod tests {
+    use crate::error::DestructError;
     use crate::linked_list::{LinkedList, LinkedListNode};
+    use crate::test_util::{on_small_stack, DEEP};
 
     #[test]
     fn from() {
@@ -409,6 +1039,100 @@ mod tests {
         );
     }
 
+    #[test]
+    fn try_add_after() {
+        let mut list = LinkedList::from(vec![1, 2, 4]);
+        assert_eq!(list.try_add_after(3, &2), Ok(()));
+        assert_eq!(
+            list.try_add_after(7, &9),
+            Err(DestructError::ElementNotFound)
+        );
+        assert_eq!(list.len(), 4);
+        assert_eq!(list, LinkedList::from(vec![1, 2, 3, 4]));
+    }
+
+    #[test]
+    #[should_panic(expected = "Cannot find LinkedListNode with value: 9")]
+    fn add_after_missing() {
+        LinkedList::from(vec![1, 2]).add_after(3, &9);
+    }
+
+    #[test]
+    fn insert_at() {
+        let mut list = LinkedList::new();
+        assert_eq!(list.insert_at(0, 2), Ok(()));
+        assert_eq!(list.insert_at(0, 0), Ok(()));
+        assert_eq!(list.insert_at(1, 1), Ok(()));
+        assert_eq!(list.insert_at(3, 3), Ok(()));
+        assert_eq!(
+            list.insert_at(5, 5),
+            Err(DestructError::IndexOutOfBounds { index: 5, len: 4 })
+        );
+        assert_eq!(list.len(), 4);
+        assert_eq!(list, LinkedList::from(vec![0, 1, 2, 3]));
+    }
+
+    #[test]
+    fn remove_at() {
+        let mut list = LinkedList::from(vec![0, 1, 2, 3]);
+        assert_eq!(list.remove_at(3), Ok(3));
+        assert_eq!(list.remove_at(1), Ok(1));
+        assert_eq!(list.remove_at(0), Ok(0));
+        assert_eq!(
+            list.remove_at(1),
+            Err(DestructError::IndexOutOfBounds { index: 1, len: 1 })
+        );
+        assert_eq!(list.len(), 1);
+        assert_eq!(list, LinkedList::from(vec![2]));
+    }
+
+    #[test]
+    fn get_by_index() {
+        let mut list = LinkedList::from(vec![10, 20, 30]);
+        assert_eq!(list.get(0), Ok(&10));
+        assert_eq!(list.get(2), Ok(&30));
+        assert_eq!(
+            list.get(3),
+            Err(DestructError::IndexOutOfBounds { index: 3, len: 3 })
+        );
+        *list.get_mut(1).unwrap() += 5;
+        assert_eq!(list.get(1), Ok(&25));
+        assert!(list.get_mut(7).is_err());
+        let empty: LinkedList<i32> = LinkedList::new();
+        assert_eq!(
+            empty.get(0),
+            Err(DestructError::IndexOutOfBounds { index: 0, len: 0 })
+        );
+    }
+
+    #[test]
+    fn swap() {
+        let mut list = LinkedList::from(vec![1, 2, 3, 4, 5]);
+        assert_eq!(list.swap(0, 4), Ok(()));
+        assert_eq!(list.swap(3, 1), Ok(()));
+        assert_eq!(list.swap(2, 2), Ok(()));
+        assert_eq!(list, LinkedList::from(vec![5, 4, 3, 2, 1]));
+        assert_eq!(list.swap(2, 3), Ok(()));
+        assert_eq!(list, LinkedList::from(vec![5, 4, 2, 3, 1]));
+        assert_eq!(
+            list.swap(1, 5),
+            Err(DestructError::IndexOutOfBounds { index: 5, len: 5 })
+        );
+        assert_eq!(list, LinkedList::from(vec![5, 4, 2, 3, 1]));
+    }
+
+    #[test]
+    fn non_comparable_elements() {
+        let mut list: LinkedList<Box<dyn Fn(i32) -> i32>> = LinkedList::new();
+        list.add(Box::new(|x| x + 1));
+        list.add_first(Box::new(|x| x * 10));
+        list.insert_at(2, Box::new(|x| x - 3)).unwrap();
+        let results: Vec<i32> = list.iter().map(|f| f(2)).collect();
+        assert_eq!(results, vec![20, 3, -1]);
+        assert_eq!(list.pop().unwrap()(1), 10);
+        assert_eq!(list.len(), 2);
+    }
+
     #[test]
     fn delete() {
         let mut deep_list = LinkedList::from(vec![1, 2, 3, 4, 5]);
@@ -647,4 +1371,382 @@ mod tests {
         }
         assert_eq!(list, LinkedList::from(vec![2, 4, 6, 8, 10]));
     }
+
+    #[test]
+    fn cursor_move_and_edit() {
+        let mut list = LinkedList::from(vec![1, 2, 3]);
+        let mut cursor = list.cursor_front_mut();
+        assert_eq!(cursor.index(), Some(0));
+        assert_eq!(cursor.current(), Some(&mut 1));
+        assert_eq!(cursor.peek_next(), Some(&mut 2));
+        cursor.move_next();
+        *cursor.current().unwrap() = 20;
+        cursor.move_next();
+        assert_eq!(cursor.peek_next(), None);
+        cursor.move_next();
+        assert_eq!(cursor.index(), None);
+        assert_eq!(cursor.current(), None);
+        cursor.move_next();
+        assert_eq!(cursor.current(), None);
+        assert_eq!(list, LinkedList::from(vec![1, 20, 3]));
+    }
+
+    #[test]
+    fn cursor_insert() {
+        let mut list = LinkedList::from(vec![2, 4]);
+        let mut cursor = list.cursor_front_mut();
+        cursor.insert_before(1);
+        assert_eq!(cursor.current(), Some(&mut 2));
+        assert_eq!(cursor.index(), Some(1));
+        cursor.insert_after(3);
+        assert_eq!(cursor.current(), Some(&mut 2));
+        cursor.move_next();
+        cursor.move_next();
+        cursor.move_next();
+        assert_eq!(cursor.current(), None);
+        cursor.insert_before(5);
+        assert_eq!(cursor.current(), None);
+        cursor.insert_after(6);
+        assert_eq!(cursor.current(), Some(&mut 6));
+        assert_eq!(list.len(), 6);
+        assert_eq!(list, LinkedList::from(vec![1, 2, 3, 4, 5, 6]));
+
+        let mut empty = LinkedList::new();
+        empty.cursor_front_mut().insert_after(1);
+        empty.cursor_front_mut().insert_before(0);
+        assert_eq!(empty, LinkedList::from(vec![0, 1]));
+    }
+
+    #[test]
+    fn cursor_remove() {
+        let mut list = LinkedList::from(vec![1, 2, 3]);
+        let mut cursor = list.cursor_front_mut();
+        assert_eq!(cursor.remove_current(), Some(1));
+        assert_eq!(cursor.current(), Some(&mut 2));
+        cursor.move_next();
+        assert_eq!(cursor.remove_current(), Some(3));
+        assert_eq!(cursor.remove_current(), None);
+        assert_eq!(list.len(), 1);
+        assert_eq!(list, LinkedList::from(vec![2]));
+    }
+
+    #[test]
+    fn cursor_split_after() {
+        let mut list = LinkedList::from(vec![1, 2, 3, 4, 5]);
+        let mut cursor = list.cursor_front_mut();
+        cursor.move_next();
+        let tail = cursor.split_after();
+        assert_eq!(cursor.peek_next(), None);
+        assert_eq!(tail.len(), 3);
+        assert_eq!(tail, LinkedList::from(vec![3, 4, 5]));
+        assert_eq!(list.len(), 2);
+        assert_eq!(list, LinkedList::from(vec![1, 2]));
+
+        let mut cursor = list.cursor_front_mut();
+        cursor.move_next();
+        cursor.move_next();
+        assert!(cursor.split_after().is_empty());
+        assert_eq!(list.len(), 2);
+    }
+
+    #[test]
+    fn cursor_single_pass_edits() {
+        // Drop odd numbers and duplicate even ones while walking the list once
+        let mut list = LinkedList::from(vec![1, 2, 3, 4, 5, 6]);
+        let mut cursor = list.cursor_front_mut();
+        while let Some(value) = cursor.current() {
+            if *value % 2 == 1 {
+                cursor.remove_current();
+            } else {
+                let copy = *value;
+                cursor.insert_before(copy);
+                cursor.move_next();
+            }
+        }
+        assert_eq!(list.len(), 6);
+        assert_eq!(list, LinkedList::from(vec![2, 2, 4, 4, 6, 6]));
+    }
+
+    #[test]
+    fn drop_long_list() {
+        on_small_stack(|| {
+            let mut list = LinkedList::new();
+            for i in 0..DEEP {
+                list.add_first(i);
+            }
+        });
+    }
+
+    #[test]
+    fn sort() {
+        let mut list = LinkedList::from(vec![5, 1, 4, 2, 3, 1]);
+        list.sort();
+        assert_eq!(list, LinkedList::from(vec![1, 1, 2, 3, 4, 5]));
+        assert_eq!(list.len(), 6);
+        list.sort_unstable_by(|a, b| b.cmp(a));
+        assert_eq!(list, LinkedList::from(vec![5, 4, 3, 2, 1, 1]));
+        let mut empty: LinkedList<i32> = LinkedList::new();
+        empty.sort();
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn sort_is_stable() {
+        let mut events = LinkedList::from(vec![(2, 'a'), (1, 'b'), (2, 'c'), (1, 'd'), (0, 'e')]);
+        events.sort_by_key(|event| event.0);
+        assert_eq!(
+            events,
+            LinkedList::from(vec![(0, 'e'), (1, 'b'), (1, 'd'), (2, 'a'), (2, 'c')])
+        );
+        events.sort_by(|a, b| b.0.cmp(&a.0));
+        assert_eq!(
+            events,
+            LinkedList::from(vec![(2, 'a'), (2, 'c'), (1, 'b'), (1, 'd'), (0, 'e')])
+        );
+    }
+
+    #[test]
+    fn sort_long_list() {
+        let mut seed: u64 = 42;
+        let mut values = Vec::new();
+        let mut list = LinkedList::new();
+        for _ in 0..100_000 {
+            seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1);
+            let value = (seed >> 33) % 1000;
+            values.push(value);
+            list.add_first(value);
+        }
+        list.sort();
+        values.sort();
+        assert!(list.is_sorted());
+        assert_eq!(list.len(), values.len());
+        assert!(list.iter().eq(values.iter()));
+    }
+
+    #[test]
+    fn reverse() {
+        let mut list = LinkedList::from(vec![1, 2, 3, 4]);
+        list.reverse();
+        assert_eq!(list, LinkedList::from(vec![4, 3, 2, 1]));
+        let mut single = LinkedList::from(vec![1]);
+        single.reverse();
+        assert_eq!(single, LinkedList::from(vec![1]));
+    }
+
+    #[test]
+    fn is_sorted() {
+        assert!(LinkedList::<i32>::new().is_sorted());
+        assert!(LinkedList::from(vec![1]).is_sorted());
+        assert!(LinkedList::from(vec![1, 1, 2, 5]).is_sorted());
+        assert!(!LinkedList::from(vec![1, 3, 2]).is_sorted());
+    }
+
+    #[test]
+    fn insert_sorted() {
+        let mut list = LinkedList::new();
+        for value in [3, 1, 4, 1, 5, 9, 2, 6] {
+            list.insert_sorted(value);
+        }
+        assert_eq!(list, LinkedList::from(vec![1, 1, 2, 3, 4, 5, 6, 9]));
+        assert_eq!(list.len(), 8);
+        let mut pairs = LinkedList::from(vec![(1, 'a'), (2, 'b')]);
+        pairs.insert_sorted((1, 'c'));
+        assert_eq!(pairs, LinkedList::from(vec![(1, 'a'), (1, 'c'), (2, 'b')]));
+    }
+
+    #[test]
+    fn merge() {
+        let mut list = LinkedList::from(vec![1, 3, 5, 7]);
+        list.merge(LinkedList::from(vec![2, 3, 4, 8, 9]));
+        assert_eq!(list, LinkedList::from(vec![1, 2, 3, 3, 4, 5, 7, 8, 9]));
+        assert_eq!(list.len(), 9);
+        list.merge(LinkedList::new());
+        assert_eq!(list.len(), 9);
+        let mut empty = LinkedList::new();
+        empty.merge(LinkedList::from(vec![1, 2]));
+        assert_eq!(empty, LinkedList::from(vec![1, 2]));
+        let mut descending = LinkedList::from(vec![(3, 'a'), (1, 'a')]);
+        descending.merge_by(LinkedList::from(vec![(3, 'b'), (2, 'b')]), |a, b| {
+            b.0.cmp(&a.0)
+        });
+        assert_eq!(
+            descending,
+            LinkedList::from(vec![(3, 'a'), (3, 'b'), (2, 'b'), (1, 'a')])
+        );
+    }
+
+    #[test]
+    fn split_off() {
+        let mut list = LinkedList::from(vec![1, 2, 3, 4, 5]);
+        let tail = list.split_off(2).unwrap();
+        assert_eq!(list, LinkedList::from(vec![1, 2]));
+        assert_eq!(tail, LinkedList::from(vec![3, 4, 5]));
+        assert_eq!((list.len(), tail.len()), (2, 3));
+        assert!(list.split_off(2).unwrap().is_empty());
+        assert_eq!(
+            list.split_off(3),
+            Err(DestructError::IndexOutOfBounds { index: 3, len: 2 })
+        );
+        let all = list.split_off(0).unwrap();
+        assert!(list.is_empty());
+        assert_eq!(all, LinkedList::from(vec![1, 2]));
+    }
+
+    #[test]
+    fn append() {
+        let mut list = LinkedList::from(vec![1, 2]);
+        let mut other = LinkedList::from(vec![3, 4]);
+        list.append(&mut other);
+        assert!(other.is_empty());
+        assert_eq!(list.len(), 4);
+        assert_eq!(list, LinkedList::from(vec![1, 2, 3, 4]));
+        let mut empty = LinkedList::new();
+        empty.append(&mut list);
+        assert_eq!(empty, LinkedList::from(vec![1, 2, 3, 4]));
+        assert!(list.is_empty());
+    }
+
+    #[test]
+    fn splice() {
+        let mut list = LinkedList::from(vec![1, 5]);
+        assert_eq!(list.splice(1, LinkedList::from(vec![2, 3, 4])), Ok(()));
+        assert_eq!(list.splice(0, LinkedList::from(vec![0])), Ok(()));
+        assert_eq!(list.splice(6, LinkedList::from(vec![6])), Ok(()));
+        assert_eq!(list.splice(3, LinkedList::new()), Ok(()));
+        assert_eq!(
+            list.splice(8, LinkedList::from(vec![8])),
+            Err(DestructError::IndexOutOfBounds { index: 8, len: 7 })
+        );
+        assert_eq!(list.len(), 7);
+        assert_eq!(list, LinkedList::from(vec![0, 1, 2, 3, 4, 5, 6]));
+    }
+
+    #[test]
+    fn truncate() {
+        let mut list = LinkedList::from(vec![1, 2, 3, 4]);
+        list.truncate(10);
+        assert_eq!(list.len(), 4);
+        list.truncate(2);
+        assert_eq!(list, LinkedList::from(vec![1, 2]));
+        assert_eq!(list.len(), 2);
+        list.truncate(0);
+        assert!(list.is_empty());
+        assert_eq!(list.get_head(), None);
+    }
+
+    #[test]
+    fn retain() {
+        let mut list = LinkedList::from(vec![1, 2, 3, 4, 5, 6]);
+        list.retain(|value| value % 2 == 0);
+        assert_eq!(list, LinkedList::from(vec![2, 4, 6]));
+        assert_eq!(list.len(), 3);
+        list.retain_mut(|value| {
+            *value *= 10;
+            *value != 40
+        });
+        assert_eq!(list, LinkedList::from(vec![20, 60]));
+        list.retain(|_| false);
+        assert!(list.is_empty());
+    }
+
+    #[test]
+    fn dedup() {
+        let mut list = LinkedList::from(vec![1, 1, 2, 3, 3, 3, 1, 4, 4]);
+        list.dedup();
+        assert_eq!(list, LinkedList::from(vec![1, 2, 3, 1, 4]));
+        assert_eq!(list.len(), 5);
+        let mut words = LinkedList::from(vec!["apple", "avocado", "banana", "blueberry", "cherry"]);
+        words.dedup_by_key(|word| word.chars().next());
+        assert_eq!(words, LinkedList::from(vec!["apple", "banana", "cherry"]));
+        let mut runs = LinkedList::from(vec![1, 2, 3, 10, 11, 20]);
+        // Each element is compared with the last kept one, not its direct predecessor
+        runs.dedup_by(|next, previous| *next - *previous < 5);
+        assert_eq!(runs, LinkedList::from(vec![1, 10, 20]));
+        let mut empty: LinkedList<i32> = LinkedList::new();
+        empty.dedup();
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn drain_filter() {
+        let mut list = LinkedList::from(vec![1, 2, 3, 4, 5, 6]);
+        let evens: Vec<i32> = list.drain_filter(|value| *value % 2 == 0).collect();
+        assert_eq!(evens, vec![2, 4, 6]);
+        assert_eq!(list, LinkedList::from(vec![1, 3, 5]));
+        assert_eq!(list.len(), 3);
+        // Stopping early leaves the unvisited elements in place
+        assert_eq!(list.drain_filter(|value| *value > 1).next(), Some(3));
+        assert_eq!(list, LinkedList::from(vec![1, 5]));
+    }
+
+    #[test]
+    fn retain_long_list() {
+        // A single pass keeps this fast; restarting from the head per removal would not
+        let mut list = LinkedList::new();
+        for i in 0..200_000 {
+            list.add_first(i);
+        }
+        list.retain(|value| value % 3 == 0);
+        assert_eq!(list.len(), 66_667);
+        assert_eq!(list.drain_filter(|value| *value % 2 == 0).count(), 33_334);
+        assert_eq!(list.len(), 33_333);
+    }
+
+    #[test]
+    fn from_iter_and_extend() {
+        let mut list: LinkedList<i32> = (1..=3).collect();
+        list.extend(vec![4, 5]);
+        assert_eq!(list.len(), 5);
+        assert_eq!(list.get_tail(), Some(&5));
+        let mut empty = LinkedList::new();
+        empty.extend(Vec::<i32>::new());
+        assert!(empty.is_empty());
+        empty.extend(list.iter().copied());
+        assert_eq!(empty, list);
+    }
+
+    #[test]
+    fn borrowed_into_iter() {
+        let mut list = LinkedList::from(vec![1, 2, 3]);
+        for element in &mut list {
+            *element += 1;
+        }
+        let mut sum = 0;
+        for element in &list {
+            sum += element;
+        }
+        assert_eq!(sum, 9);
+    }
+
+    #[test]
+    fn std_traits() {
+        use std::collections::HashSet;
+
+        let list = LinkedList::from(vec![1, 2, 3]);
+        let mut copy = list.clone();
+        assert_eq!(list, copy);
+        copy.add(4);
+        assert_ne!(list, copy);
+        assert_eq!(list.len(), 3);
+        assert_eq!(format!("{:?}", list), "[1, 2, 3]");
+        assert_eq!(LinkedList::<i32>::default(), LinkedList::new());
+        assert!(list < copy);
+        assert!(LinkedList::from(vec![1, 3]) > list);
+        assert_eq!(
+            LinkedList::from(vec![1.0, f64::NAN]).partial_cmp(&LinkedList::from(vec![1.0, 2.0])),
+            None
+        );
+        let set: HashSet<LinkedList<i32>> = vec![list.clone(), list, copy].into_iter().collect();
+        assert_eq!(set.len(), 2);
+    }
+
+    #[test]
+    fn compare_long_lists() {
+        on_small_stack(|| {
+            let list: LinkedList<i32> = (0..DEEP).collect();
+            let copy = list.clone();
+            assert!(list == copy);
+            assert_eq!(list.cmp(&copy), std::cmp::Ordering::Equal);
+        });
+    }
 }
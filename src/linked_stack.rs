@@ -1,3 +1,10 @@
+use crate::stack_like::StackLike;
+use std::cmp::Ordering;
+use std::fmt::{self, Debug};
+use std::hash::{Hash, Hasher};
+use std::iter::FromIterator;
+
+/// Stack of linked nodes. Iteration, formatting and ordering all go from the top down.
 pub struct LinkedStack<T> {
     size: usize,
     head: Option<Box<LinkedStackNode<T>>>,
@@ -8,6 +15,12 @@ struct LinkedStackNode<T> {
     next: Option<Box<LinkedStackNode<T>>>,
 }
 
+impl<T> Default for LinkedStack<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl<T> LinkedStack<T> {
     pub fn push(&mut self, new_value: T) {
         let old_head = self.head.take();
@@ -30,6 +43,15 @@ impl<T> LinkedStack<T> {
         self.head.as_ref().map(|head| &head.data)
     }
 
+    pub fn peek_mut(&mut self) -> Option<&mut T> {
+        self.head.as_mut().map(|head| &mut head.data)
+    }
+
+    pub fn clear(&mut self) {
+        // Dropping the old stack releases the nodes iteratively
+        *self = LinkedStack::new();
+    }
+
     pub fn new() -> Self {
         LinkedStack {
             size: 0,
@@ -40,20 +62,192 @@ impl<T> LinkedStack<T> {
     pub fn len(&self) -> usize {
         self.size
     }
+
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            next: self.head.as_deref(),
+            len: self.size,
+        }
+    }
+}
+
+impl<T> StackLike<T> for LinkedStack<T> {
+    fn push(&mut self, element: T) {
+        self.push(element);
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        self.pop()
+    }
+
+    fn peek(&self) -> Option<&T> {
+        self.peek()
+    }
+
+    fn peek_mut(&mut self) -> Option<&mut T> {
+        self.peek_mut()
+    }
+
+    fn len(&self) -> usize {
+        self.len()
+    }
+
+    fn clear(&mut self) {
+        self.clear();
+    }
+}
+
+impl<T: Clone> Clone for LinkedStack<T> {
+    fn clone(&self) -> Self {
+        let mut clone = LinkedStack::new();
+        let mut link = &mut clone.head;
+        for element in self.iter() {
+            let node = link.insert(Box::from(LinkedStackNode {
+                data: element.clone(),
+                next: None,
+            }));
+            link = &mut node.next;
+        }
+        clone.size = self.size;
+        clone
+    }
+}
+
+impl<T: Debug> Debug for LinkedStack<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: PartialEq> PartialEq for LinkedStack<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.size == other.size && self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Eq> Eq for LinkedStack<T> {}
+
+impl<T: PartialOrd> PartialOrd for LinkedStack<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.iter().partial_cmp(other.iter())
+    }
+}
+
+impl<T: Ord> Ord for LinkedStack<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.iter().cmp(other.iter())
+    }
+}
+
+impl<T: Hash> Hash for LinkedStack<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.size.hash(state);
+        for element in self.iter() {
+            element.hash(state);
+        }
+    }
+}
+
+/// Pushes the elements in order, so the last one ends up on top.
+impl<T> FromIterator<T> for LinkedStack<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut stack = LinkedStack::new();
+        stack.extend(iter);
+        stack
+    }
+}
+
+impl<T> Extend<T> for LinkedStack<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for element in iter {
+            self.push(element);
+        }
+    }
+}
+
+impl<T> IntoIterator for LinkedStack<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter(self)
+    }
+}
+
+impl<'a, T> IntoIterator for &'a LinkedStack<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+pub struct IntoIter<T>(LinkedStack<T>);
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.pop()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.0.size, Some(self.0.size))
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+pub struct Iter<'a, T> {
+    next: Option<&'a LinkedStackNode<T>>,
+    len: usize,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next.map(|node| {
+            self.next = node.next.as_deref();
+            self.len -= 1;
+            &node.data
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, T> ExactSizeIterator for Iter<'a, T> {}
+
+// Takes the `next` boxes apart in a loop instead of letting them drop recursively
+impl<T> Drop for LinkedStack<T> {
+    fn drop(&mut self) {
+        let mut link = self.head.take();
+        while let Some(mut node) = link {
+            link = node.next.take();
+        }
+    }
 }
 
 impl<T> From<Vec<T>> for LinkedStack<T> {
     fn from(vec: Vec<T>) -> LinkedStack<T> {
-        vec.into_iter().fold(LinkedStack::new(), |mut stack, elem| {
-            stack.push(elem);
-            stack
-        })
+        vec.into_iter().collect()
     }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::linked_stack::LinkedStack;
+    use crate::stack_like::stack_like_tests;
+
+    stack_like_tests!(stack_like, LinkedStack<i32>);
 
     #[test]
     fn push() {
@@ -122,4 +316,43 @@ mod tests {
         stack.push(3);
         assert_eq!(stack.len(), 3);
     }
+
+    #[test]
+    fn iter_from_top() {
+        let stack = LinkedStack::from(vec![1, 2, 3]);
+        let mut iter = stack.iter();
+        assert_eq!(iter.len(), 3);
+        assert_eq!(iter.next(), Some(&3));
+        assert_eq!(iter.len(), 2);
+        assert_eq!((&stack).into_iter().count(), 3);
+        assert_eq!(stack.into_iter().collect::<Vec<_>>(), vec![3, 2, 1]);
+    }
+
+    #[test]
+    fn from_iter_and_extend() {
+        let mut stack: LinkedStack<i32> = (1..=3).collect();
+        assert_eq!(stack.peek(), Some(&3));
+        stack.extend(vec![4, 5]);
+        assert_eq!(stack.pop(), Some(5));
+        assert_eq!(stack.len(), 4);
+    }
+
+    #[test]
+    fn std_traits() {
+        use std::collections::HashSet;
+
+        let stack = LinkedStack::from(vec![1, 2, 3]);
+        let mut copy = stack.clone();
+        assert_eq!(stack, copy);
+        assert_eq!(copy.len(), 3);
+        assert_eq!(copy.pop(), Some(3));
+        assert_ne!(stack, copy);
+        assert_eq!(format!("{:?}", stack), "[3, 2, 1]");
+        assert_eq!(LinkedStack::<i32>::default(), LinkedStack::new());
+        // Compared from the top down
+        assert!(LinkedStack::from(vec![9, 1]) < LinkedStack::from(vec![1, 2]));
+        assert!(LinkedStack::from(vec![1]) < LinkedStack::from(vec![0, 1]));
+        let set: HashSet<LinkedStack<i32>> = vec![stack.clone(), stack, copy].into_iter().collect();
+        assert_eq!(set.len(), 2);
+    }
 }
@@ -0,0 +1,1019 @@
+use crate::error::DestructError;
+use crate::priority_queue::{Comparator, Natural, PriorityQueue};
+use std::cell::{Cell, RefCell};
+use std::cmp::Ordering;
+use std::fmt::{self, Debug};
+use std::iter::FromIterator;
+use std::marker::PhantomData;
+use std::mem;
+use std::ptr::NonNull;
+use std::rc::Rc;
+
+/// Pairing heap: O(1) `push` and `meld`, amortised O(log n) `pop`, `promote` and
+/// `delete`. Pops the greatest element first like `BinaryHeap`; use the `Reversed`
+/// comparator for a min-heap.
+pub struct PairingHeap<T, C = Natural> {
+    root: Option<NonNull<Node<T>>>,
+    len: usize,
+    comparator: C,
+    owner: Rc<Owner>,
+    marker: PhantomData<Box<Node<T>>>,
+}
+
+/// Fibonacci heap: O(1) `push`, `meld` and amortised `promote`, amortised O(log n)
+/// `pop` and `delete`. Orders its elements the same way as `PairingHeap`.
+pub struct FibonacciHeap<T, C = Natural> {
+    top: Option<NonNull<Node<T>>>,
+    len: usize,
+    comparator: C,
+    owner: Rc<Owner>,
+    marker: PhantomData<Box<Node<T>>>,
+}
+
+/// Refers to an element pushed into a `PairingHeap` or `FibonacciHeap`. It stays valid
+/// when its heap is melded into another one, and goes stale once the element is popped
+/// or deleted, after which the heap reports `DestructError::ElementNotFound` for it.
+pub struct Handle<T>(Rc<Entry<T>>);
+
+// Both heaps keep their trees as circular doubly linked sibling lists, so any node can be
+// cut out in O(1). `degree` counts the children and `marked` is only used by the
+// Fibonacci heap. Every unsafe block relies on the links only pointing to live nodes
+// owned by the same heap.
+struct Node<T> {
+    element: T,
+    parent: Option<NonNull<Node<T>>>,
+    child: Option<NonNull<Node<T>>>,
+    prev: NonNull<Node<T>>,
+    next: NonNull<Node<T>>,
+    degree: usize,
+    marked: bool,
+    entry: Rc<Entry<T>>,
+}
+
+type NodePtr<T> = NonNull<Node<T>>;
+
+// Shared between a node and its handles. `node` is cleared when the node is freed.
+struct Entry<T> {
+    node: Cell<Option<NonNull<Node<T>>>>,
+    owner: RefCell<Rc<Owner>>,
+}
+
+// Identifies the heap a node lives in. A heap melded into another one forwards to the
+// other heap's owner, so `meld` does not have to visit the nodes it moves.
+struct Owner {
+    forward: RefCell<Option<Rc<Owner>>>,
+}
+
+impl Owner {
+    fn new() -> Rc<Owner> {
+        Rc::new(Owner {
+            forward: RefCell::new(None),
+        })
+    }
+
+    fn forward_to(&self, owner: &Rc<Owner>) {
+        *self.forward.borrow_mut() = Some(owner.clone());
+    }
+}
+
+impl<T> Handle<T> {
+    // Returns the node behind the handle if it is still alive and owned by `owner`
+    fn node(&self, owner: &Rc<Owner>) -> Result<NonNull<Node<T>>, DestructError> {
+        let node = self.0.node.get().ok_or(DestructError::ElementNotFound)?;
+        let mut current = self.0.owner.borrow().clone();
+        loop {
+            let next = current.forward.borrow().clone();
+            match next {
+                Some(next) => current = next,
+                None => break,
+            }
+        }
+        let owned = Rc::ptr_eq(&current, owner);
+        *self.0.owner.borrow_mut() = current;
+        if owned {
+            Ok(node)
+        } else {
+            Err(DestructError::ElementNotFound)
+        }
+    }
+}
+
+impl<T> Clone for Handle<T> {
+    fn clone(&self) -> Self {
+        Handle(self.0.clone())
+    }
+}
+
+impl<T> Debug for Handle<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Handle")
+            .field("alive", &self.0.node.get().is_some())
+            .finish()
+    }
+}
+
+fn new_node<T>(element: T, owner: &Rc<Owner>) -> (NonNull<Node<T>>, Handle<T>) {
+    let entry = Rc::new(Entry {
+        node: Cell::new(None),
+        owner: RefCell::new(owner.clone()),
+    });
+    let node = NonNull::from(Box::leak(Box::new(Node {
+        element,
+        parent: None,
+        child: None,
+        prev: NonNull::dangling(),
+        next: NonNull::dangling(),
+        degree: 0,
+        marked: false,
+        entry: entry.clone(),
+    })));
+    // SAFETY: the node was just allocated and nothing else points to it yet
+    unsafe {
+        (*node.as_ptr()).prev = node;
+        (*node.as_ptr()).next = node;
+    }
+    entry.node.set(Some(node));
+    (node, Handle(entry))
+}
+
+// Joins two circular lists so that the list of `b` follows `a`
+unsafe fn splice<T>(a: NonNull<Node<T>>, b: NonNull<Node<T>>) {
+    let a_next = (*a.as_ptr()).next;
+    let b_prev = (*b.as_ptr()).prev;
+    (*a.as_ptr()).next = b;
+    (*b.as_ptr()).prev = a;
+    (*b_prev.as_ptr()).next = a_next;
+    (*a_next.as_ptr()).prev = b_prev;
+}
+
+// Cuts `node` out of its sibling list and away from its parent, leaving it alone in a
+// list of its own with its subtree still attached
+unsafe fn detach<T>(node: NonNull<Node<T>>) {
+    let next = (*node.as_ptr()).next;
+    let prev = (*node.as_ptr()).prev;
+    if let Some(parent) = (*node.as_ptr()).parent.take() {
+        if (*parent.as_ptr()).child == Some(node) {
+            (*parent.as_ptr()).child = if next == node { None } else { Some(next) };
+        }
+        (*parent.as_ptr()).degree -= 1;
+    }
+    (*prev.as_ptr()).next = next;
+    (*next.as_ptr()).prev = prev;
+    (*node.as_ptr()).prev = node;
+    (*node.as_ptr()).next = node;
+}
+
+// Makes `child`, which must be alone in its list, the first child of `parent`
+unsafe fn add_child<T>(parent: NonNull<Node<T>>, child: NonNull<Node<T>>) {
+    if let Some(first) = (*parent.as_ptr()).child {
+        splice((*first.as_ptr()).prev, child);
+    }
+    (*parent.as_ptr()).child = Some(child);
+    (*parent.as_ptr()).degree += 1;
+    (*child.as_ptr()).parent = Some(parent);
+}
+
+// Breaks the circular list containing `head` into nodes that are each alone in a list,
+// clearing their parent links and marks
+unsafe fn split<T>(head: NonNull<Node<T>>) -> Vec<NonNull<Node<T>>> {
+    let mut nodes = Vec::new();
+    let mut current = head;
+    loop {
+        let next = (*current.as_ptr()).next;
+        (*current.as_ptr()).prev = current;
+        (*current.as_ptr()).next = current;
+        (*current.as_ptr()).parent = None;
+        (*current.as_ptr()).marked = false;
+        nodes.push(current);
+        if next == head {
+            return nodes;
+        }
+        current = next;
+    }
+}
+
+// Takes the children of `node` out as separate single-node lists
+unsafe fn take_children<T>(node: NonNull<Node<T>>) -> Vec<NonNull<Node<T>>> {
+    (*node.as_ptr()).degree = 0;
+    match (*node.as_ptr()).child.take() {
+        Some(child) => split(child),
+        None => Vec::new(),
+    }
+}
+
+// Frees a node whose links no longer matter, invalidating its handles
+unsafe fn free<T>(node: NonNull<Node<T>>) -> T {
+    let node = *Box::from_raw(node.as_ptr());
+    node.entry.node.set(None);
+    node.element
+}
+
+// Frees every node of the forest whose roots are in the list containing `head`. Walks
+// the trees with an explicit stack so that deep trees cannot overflow the call stack.
+unsafe fn free_all<T>(head: Option<NonNull<Node<T>>>) {
+    let mut stack = match head {
+        Some(head) => split(head),
+        None => return,
+    };
+    while let Some(node) = stack.pop() {
+        stack.extend(take_children(node));
+        free(node);
+    }
+}
+
+unsafe fn element<'a, T>(node: NonNull<Node<T>>) -> &'a T {
+    &(*node.as_ptr()).element
+}
+
+// Whether `a` has to be popped before `b`, using the same convention as `BinaryHeap`
+unsafe fn precedes<T, C: Comparator<T>>(
+    comparator: &C,
+    a: NonNull<Node<T>>,
+    b: NonNull<Node<T>>,
+) -> bool {
+    comparator.compare(element(a), element(b)) == Ordering::Greater
+}
+
+// Checks that `element` would not be popped later than the one in `node` and swaps it in
+unsafe fn replace_element<T, C: Comparator<T>>(
+    comparator: &C,
+    node: NonNull<Node<T>>,
+    element: T,
+) -> Result<T, DestructError> {
+    if comparator.compare(&element, &(*node.as_ptr()).element) == Ordering::Less {
+        return Err(DestructError::InvalidPriority);
+    }
+    Ok(mem::replace(&mut (*node.as_ptr()).element, element))
+}
+
+impl<T: Ord> PairingHeap<T> {
+    pub fn new() -> Self {
+        Self::with_comparator(Natural)
+    }
+}
+
+impl<T, C: Comparator<T>> PairingHeap<T, C> {
+    pub fn with_comparator(comparator: C) -> Self {
+        PairingHeap {
+            root: None,
+            len: 0,
+            comparator,
+            owner: Owner::new(),
+            marker: PhantomData,
+        }
+    }
+
+    pub fn push(&mut self, element: T) -> Handle<T> {
+        let (node, handle) = new_node(element, &self.owner);
+        self.root = Some(match self.root {
+            Some(root) => self.link(root, node),
+            None => node,
+        });
+        self.len += 1;
+        handle
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        let root = self.root?;
+        // SAFETY: `root` is a live node of this heap and is freed only after its children
+        // have been moved out
+        unsafe {
+            let children = take_children(root);
+            self.root = self.merge_pairs(children);
+            self.len -= 1;
+            Some(free(root))
+        }
+    }
+
+    pub fn peek(&self) -> Option<&T> {
+        // SAFETY: the root lives as long as the heap is not modified
+        self.root.map(|root| unsafe { element(root) })
+    }
+
+    /// Returns the element behind `handle` if it is still in this heap.
+    pub fn get(&self, handle: &Handle<T>) -> Option<&T> {
+        let node = handle.node(&self.owner).ok()?;
+        // SAFETY: `node` checked that the node is alive and owned by this heap
+        Some(unsafe { element(node) })
+    }
+
+    /// Moves every element of `other` into this heap in O(1). Handles issued by `other`
+    /// keep working with this heap.
+    pub fn meld(&mut self, mut other: PairingHeap<T, C>) {
+        other.owner.forward_to(&self.owner);
+        if let Some(other_root) = other.root.take() {
+            self.root = Some(match self.root {
+                Some(root) => self.link(root, other_root),
+                None => other_root,
+            });
+        }
+        self.len += mem::replace(&mut other.len, 0);
+    }
+
+    /// Moves the element behind `handle` towards the top by replacing it with one that is
+    /// popped no later than it, and returns the old element. Fails with
+    /// `DestructError::InvalidPriority` if the new element would be popped later. This is
+    /// increase-key in the default order and decrease-key with `Reversed`.
+    pub fn promote(&mut self, handle: &Handle<T>, element: T) -> Result<T, DestructError> {
+        let node = handle.node(&self.owner)?;
+        // SAFETY: `node` is a live node of this heap, and a detached subtree is always
+        // linked back under the root
+        unsafe {
+            let old = replace_element(&self.comparator, node, element)?;
+            if let Some(root) = self.root.filter(|&root| root != node) {
+                detach(node);
+                self.root = Some(self.link(root, node));
+            }
+            Ok(old)
+        }
+    }
+
+    /// Removes the element behind `handle` from the heap.
+    pub fn delete(&mut self, handle: &Handle<T>) -> Result<T, DestructError> {
+        let node = handle.node(&self.owner)?;
+        match self.root {
+            Some(root) if root != node => {
+                // SAFETY: `node` is a live node of this heap below the root, and its
+                // children are linked back under the root before it is freed
+                unsafe {
+                    detach(node);
+                    let children = take_children(node);
+                    if let Some(subtree) = self.merge_pairs(children) {
+                        self.root = Some(self.link(root, subtree));
+                    }
+                    self.len -= 1;
+                    Ok(free(node))
+                }
+            }
+            _ => Ok(self.pop().unwrap()),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn clear(&mut self) {
+        // SAFETY: every node is reachable from the root and freed exactly once
+        unsafe { free_all(self.root.take()) };
+        self.len = 0;
+    }
+
+    /// Iterates over the elements in no particular order.
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter::new(self.root, self.len)
+    }
+
+    // Links two single-node lists, returning the one that now holds the other as a child
+    fn link(&self, a: NonNull<Node<T>>, b: NonNull<Node<T>>) -> NonNull<Node<T>> {
+        // SAFETY: both nodes are live roots of this heap
+        unsafe {
+            let (parent, child) = if precedes(&self.comparator, b, a) {
+                (b, a)
+            } else {
+                (a, b)
+            };
+            add_child(parent, child);
+            parent
+        }
+    }
+
+    // Two-pass pairing: links neighbours left to right, then folds the results from the
+    // right into a single tree
+    fn merge_pairs(&self, nodes: Vec<NonNull<Node<T>>>) -> Option<NonNull<Node<T>>> {
+        let paired: Vec<_> = nodes
+            .chunks(2)
+            .map(|pair| match *pair {
+                [a, b] => self.link(a, b),
+                [a] => a,
+                _ => unreachable!(),
+            })
+            .collect();
+        paired
+            .into_iter()
+            .rev()
+            .reduce(|tree, node| self.link(node, tree))
+    }
+}
+
+impl<T: Ord> FibonacciHeap<T> {
+    pub fn new() -> Self {
+        Self::with_comparator(Natural)
+    }
+}
+
+impl<T, C: Comparator<T>> FibonacciHeap<T, C> {
+    pub fn with_comparator(comparator: C) -> Self {
+        FibonacciHeap {
+            top: None,
+            len: 0,
+            comparator,
+            owner: Owner::new(),
+            marker: PhantomData,
+        }
+    }
+
+    pub fn push(&mut self, element: T) -> Handle<T> {
+        let (node, handle) = new_node(element, &self.owner);
+        self.add_root(node);
+        self.len += 1;
+        handle
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        let top = self.top?;
+        // SAFETY: `top` is a live node of this heap, and every other root and child of
+        // `top` is consolidated back into the heap before it is freed
+        unsafe {
+            let mut roots = split(top);
+            roots.retain(|&root| root != top);
+            roots.extend(take_children(top));
+            self.top = self.consolidate(roots);
+            self.len -= 1;
+            Some(free(top))
+        }
+    }
+
+    pub fn peek(&self) -> Option<&T> {
+        // SAFETY: the top node lives as long as the heap is not modified
+        self.top.map(|top| unsafe { element(top) })
+    }
+
+    /// Returns the element behind `handle` if it is still in this heap.
+    pub fn get(&self, handle: &Handle<T>) -> Option<&T> {
+        let node = handle.node(&self.owner).ok()?;
+        // SAFETY: `node` checked that the node is alive and owned by this heap
+        Some(unsafe { element(node) })
+    }
+
+    /// Moves every element of `other` into this heap in O(1). Handles issued by `other`
+    /// keep working with this heap.
+    pub fn meld(&mut self, mut other: FibonacciHeap<T, C>) {
+        other.owner.forward_to(&self.owner);
+        if let Some(other_top) = other.top.take() {
+            self.add_root(other_top);
+        }
+        self.len += mem::replace(&mut other.len, 0);
+    }
+
+    /// Moves the element behind `handle` towards the top, like `PairingHeap::promote`.
+    pub fn promote(&mut self, handle: &Handle<T>, element: T) -> Result<T, DestructError> {
+        let node = handle.node(&self.owner)?;
+        // SAFETY: `node` is a live node of this heap, and cut nodes go to the root list
+        unsafe {
+            let old = replace_element(&self.comparator, node, element)?;
+            if let Some(parent) = (*node.as_ptr()).parent {
+                if precedes(&self.comparator, node, parent) {
+                    self.cut(node);
+                }
+            }
+            if precedes(&self.comparator, node, self.top.unwrap()) {
+                self.top = Some(node);
+            }
+            Ok(old)
+        }
+    }
+
+    /// Removes the element behind `handle` from the heap.
+    pub fn delete(&mut self, handle: &Handle<T>) -> Result<T, DestructError> {
+        let node = handle.node(&self.owner)?;
+        // SAFETY: `node` is a live node of this heap. Once it is a root it can be popped
+        // like the top, since `pop` recomputes the top from every remaining root
+        unsafe {
+            if (*node.as_ptr()).parent.is_some() {
+                self.cut(node);
+            }
+        }
+        self.top = Some(node);
+        Ok(self.pop().unwrap())
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn clear(&mut self) {
+        // SAFETY: every node is reachable from the root list and freed exactly once
+        unsafe { free_all(self.top.take()) };
+        self.len = 0;
+    }
+
+    /// Iterates over the elements in no particular order.
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter::new(self.top, self.len)
+    }
+
+    // Adds a list of roots to the root list and updates the top
+    fn add_root(&mut self, node: NonNull<Node<T>>) {
+        // SAFETY: `node` heads a list of live roots that are not in the root list yet
+        unsafe {
+            match self.top {
+                Some(top) => {
+                    splice(top, node);
+                    if precedes(&self.comparator, node, top) {
+                        self.top = Some(node);
+                    }
+                }
+                None => self.top = Some(node),
+            }
+        }
+    }
+
+    // Moves `node` to the root list, then keeps cutting ancestors that already lost a
+    // child since they were linked under their parent
+    unsafe fn cut(&mut self, node: NonNull<Node<T>>) {
+        let mut node = node;
+        while let Some(parent) = (*node.as_ptr()).parent {
+            detach(node);
+            (*node.as_ptr()).marked = false;
+            self.add_root(node);
+            if (*parent.as_ptr()).parent.is_none() {
+                break;
+            }
+            if !(*parent.as_ptr()).marked {
+                (*parent.as_ptr()).marked = true;
+                break;
+            }
+            node = parent;
+        }
+    }
+
+    // Links roots of equal degree until every degree is unique, and returns the new top
+    unsafe fn consolidate(&self, roots: Vec<NonNull<Node<T>>>) -> Option<NonNull<Node<T>>> {
+        let mut by_degree: Vec<Option<NonNull<Node<T>>>> = Vec::new();
+        for mut root in roots {
+            let mut degree = (*root.as_ptr()).degree;
+            loop {
+                if degree >= by_degree.len() {
+                    by_degree.resize(degree + 1, None);
+                }
+                match by_degree[degree].take() {
+                    Some(mut other) => {
+                        if precedes(&self.comparator, other, root) {
+                            mem::swap(&mut root, &mut other);
+                        }
+                        add_child(root, other);
+                        degree += 1;
+                    }
+                    None => break,
+                }
+            }
+            by_degree[degree] = Some(root);
+        }
+        let mut top: Option<NonNull<Node<T>>> = None;
+        for root in by_degree.into_iter().flatten() {
+            top = Some(match top {
+                Some(top) => {
+                    splice(top, root);
+                    if precedes(&self.comparator, root, top) {
+                        root
+                    } else {
+                        top
+                    }
+                }
+                None => root,
+            });
+        }
+        top
+    }
+}
+
+impl<T, C> Drop for PairingHeap<T, C> {
+    fn drop(&mut self) {
+        // SAFETY: every node is reachable from the root and freed exactly once
+        unsafe { free_all(self.root.take()) };
+    }
+}
+
+impl<T, C> Drop for FibonacciHeap<T, C> {
+    fn drop(&mut self) {
+        // SAFETY: every node is reachable from the root list and freed exactly once
+        unsafe { free_all(self.top.take()) };
+    }
+}
+
+impl<T, C: Comparator<T>> PriorityQueue<T> for PairingHeap<T, C> {
+    fn push(&mut self, element: T) {
+        self.push(element);
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        self.pop()
+    }
+
+    fn peek(&self) -> Option<&T> {
+        self.peek()
+    }
+
+    fn len(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<T, C: Comparator<T>> PriorityQueue<T> for FibonacciHeap<T, C> {
+    fn push(&mut self, element: T) {
+        self.push(element);
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        self.pop()
+    }
+
+    fn peek(&self) -> Option<&T> {
+        self.peek()
+    }
+
+    fn len(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<T, C: Comparator<T> + Default> Default for PairingHeap<T, C> {
+    fn default() -> Self {
+        Self::with_comparator(C::default())
+    }
+}
+
+impl<T, C: Comparator<T> + Default> Default for FibonacciHeap<T, C> {
+    fn default() -> Self {
+        Self::with_comparator(C::default())
+    }
+}
+
+impl<T: Debug, C> Debug for PairingHeap<T, C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list()
+            .entries(Iter::new(self.root, self.len))
+            .finish()
+    }
+}
+
+impl<T: Debug, C> Debug for FibonacciHeap<T, C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list()
+            .entries(Iter::new(self.top, self.len))
+            .finish()
+    }
+}
+
+impl<T, C: Comparator<T> + Default> FromIterator<T> for PairingHeap<T, C> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut heap = Self::default();
+        heap.extend(iter);
+        heap
+    }
+}
+
+impl<T, C: Comparator<T> + Default> FromIterator<T> for FibonacciHeap<T, C> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut heap = Self::default();
+        heap.extend(iter);
+        heap
+    }
+}
+
+impl<T, C: Comparator<T>> Extend<T> for PairingHeap<T, C> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for element in iter {
+            self.push(element);
+        }
+    }
+}
+
+impl<T, C: Comparator<T>> Extend<T> for FibonacciHeap<T, C> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for element in iter {
+            self.push(element);
+        }
+    }
+}
+
+impl<'a, T, C: Comparator<T>> IntoIterator for &'a PairingHeap<T, C> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T, C: Comparator<T>> IntoIterator for &'a FibonacciHeap<T, C> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Walks a heap depth first, keeping the lists of siblings it still has to visit.
+pub struct Iter<'a, T> {
+    pending: Vec<(NodePtr<T>, NodePtr<T>)>,
+    len: usize,
+    marker: PhantomData<&'a Node<T>>,
+}
+
+impl<'a, T> Iter<'a, T> {
+    fn new(head: Option<NonNull<Node<T>>>, len: usize) -> Self {
+        Iter {
+            pending: head.map(|head| (head, head)).into_iter().collect(),
+            len,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // Each pending entry is the next node to visit and the head of its sibling list
+        let (node, head) = self.pending.pop()?;
+        // SAFETY: the heap is borrowed for `'a`, so all of its nodes stay alive
+        unsafe {
+            let next = (*node.as_ptr()).next;
+            if next != head {
+                self.pending.push((next, head));
+            }
+            if let Some(child) = (*node.as_ptr()).child {
+                self.pending.push((child, child));
+            }
+            self.len -= 1;
+            Some(element(node))
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, T> ExactSizeIterator for Iter<'a, T> {}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::DestructError;
+    use crate::mergeable_heap::{FibonacciHeap, Handle, PairingHeap};
+    use crate::priority_queue::{BinaryHeap, PriorityQueue, Reversed};
+    use crate::test_util::{on_small_stack, DEEP};
+    use std::cell::Cell;
+    use std::mem;
+    use std::rc::Rc;
+
+    // Operation count of the randomized tests, reduced under Miri
+    const STEPS: usize = if cfg!(miri) { 200 } else { 3000 };
+
+    // Deterministic pseudo-random numbers, good enough to shuffle test input
+    struct Lcg(u64);
+
+    impl Lcg {
+        fn next(&mut self) -> u64 {
+            self.0 = self
+                .0
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            self.0 >> 33
+        }
+    }
+
+    fn drain<T, Q: PriorityQueue<T>>(queue: &mut Q) -> Vec<T> {
+        let mut result = Vec::new();
+        while let Some(element) = queue.pop() {
+            result.push(element);
+        }
+        result
+    }
+
+    // Checks a queue in its default order against a sorted `Vec` through the shared trait
+    fn check_against_sorted_vec<Q: PriorityQueue<u64> + Default>() {
+        let mut random = Lcg(7);
+        let mut queue = Q::default();
+        let mut reference: Vec<u64> = Vec::new();
+        for round in 0..STEPS {
+            if round % 3 == 2 {
+                reference.sort_unstable();
+                assert_eq!(queue.pop(), reference.pop());
+            } else {
+                let element = random.next() % 500;
+                queue.push(element);
+                reference.push(element);
+            }
+            assert_eq!(queue.len(), reference.len());
+        }
+        reference.sort_unstable_by(|a, b| b.cmp(a));
+        assert_eq!(queue.peek(), reference.first());
+        assert_eq!(drain(&mut queue), reference);
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn pairing_heap_matches_sorted_vec() {
+        check_against_sorted_vec::<PairingHeap<u64>>();
+    }
+
+    #[test]
+    fn fibonacci_heap_matches_sorted_vec() {
+        check_against_sorted_vec::<FibonacciHeap<u64>>();
+    }
+
+    #[test]
+    fn binary_heap_matches_sorted_vec() {
+        check_against_sorted_vec::<BinaryHeap<u64>>();
+    }
+
+    #[test]
+    fn defaults_pop_in_the_same_order() {
+        let elements = vec![3, 9, 1, 7];
+        let mut binary: BinaryHeap<i32> = elements.iter().copied().collect();
+        let mut pairing: PairingHeap<i32> = elements.iter().copied().collect();
+        let mut fibonacci: FibonacciHeap<i32> = elements.into_iter().collect();
+        let expected = drain(&mut binary);
+        assert_eq!(expected, vec![9, 7, 3, 1]);
+        assert_eq!(drain(&mut pairing), expected);
+        assert_eq!(drain(&mut fibonacci), expected);
+    }
+
+    macro_rules! handle_tests {
+        ($name:ident, $heap:ident) => {
+            mod $name {
+                use super::*;
+
+                #[test]
+                fn promote_in_default_order() {
+                    let mut heap = $heap::new();
+                    heap.push(1);
+                    heap.push(5);
+                    let handle = heap.push(3);
+                    assert_eq!(heap.promote(&handle, 9), Ok(3));
+                    assert_eq!(heap.peek(), Some(&9));
+                    assert_eq!(
+                        heap.promote(&handle, 0),
+                        Err(DestructError::InvalidPriority)
+                    );
+                    assert_eq!(drain(&mut heap), vec![9, 5, 1]);
+                }
+
+                #[test]
+                fn meld() {
+                    let mut first: $heap<i32, Reversed> = vec![5, 1, 9].into_iter().collect();
+                    let mut second = $heap::with_comparator(Reversed);
+                    let handle = second.push(7);
+                    second.extend(vec![0, 4]);
+                    first.meld(second);
+                    first.meld($heap::with_comparator(Reversed));
+                    assert_eq!(first.len(), 6);
+                    assert_eq!(first.get(&handle), Some(&7));
+                    assert_eq!(first.promote(&handle, 2), Ok(7));
+                    assert_eq!(drain(&mut first), vec![0, 1, 2, 4, 5, 9]);
+                }
+
+                #[test]
+                fn promote_and_delete() {
+                    let mut heap = $heap::with_comparator(Reversed);
+                    let handles: Vec<_> = (0..20).map(|i| heap.push(i * 10)).collect();
+                    assert_eq!(heap.pop(), Some(0));
+                    assert_eq!(heap.promote(&handles[15], 5), Ok(150));
+                    assert_eq!(heap.peek(), Some(&5));
+                    assert_eq!(
+                        heap.promote(&handles[3], 40),
+                        Err(DestructError::InvalidPriority)
+                    );
+                    assert_eq!(heap.delete(&handles[15]), Ok(5));
+                    assert_eq!(heap.delete(&handles[7]), Ok(70));
+                    assert_eq!(
+                        heap.delete(&handles[7]),
+                        Err(DestructError::ElementNotFound)
+                    );
+                    assert_eq!(
+                        heap.promote(&handles[0], 0),
+                        Err(DestructError::ElementNotFound)
+                    );
+                    assert_eq!(heap.get(&handles[0]), None);
+                    assert_eq!(heap.len(), 17);
+                    let expected: Vec<_> = (1..20)
+                        .filter(|&i| i != 7 && i != 15)
+                        .map(|i| i * 10)
+                        .collect();
+                    assert_eq!(drain(&mut heap), expected);
+                }
+
+                #[test]
+                fn rejects_handles_of_other_heaps() {
+                    let mut heap = $heap::with_comparator(Reversed);
+                    let mut other = $heap::with_comparator(Reversed);
+                    heap.push(1);
+                    let foreign: Handle<i32> = other.push(2);
+                    assert_eq!(heap.get(&foreign), None);
+                    assert_eq!(heap.delete(&foreign), Err(DestructError::ElementNotFound));
+                    assert_eq!(
+                        heap.promote(&foreign, 0),
+                        Err(DestructError::ElementNotFound)
+                    );
+                    assert_eq!(other.delete(&foreign), Ok(2));
+                    assert_eq!(heap.len(), 1);
+                }
+
+                #[test]
+                fn random_operations_match_sorted_vec() {
+                    let mut random = Lcg(42);
+                    let mut heap = $heap::with_comparator(Reversed);
+                    let mut live: Vec<(Handle<u64>, u64)> = Vec::new();
+                    for _ in 0..STEPS {
+                        match random.next() % 5 {
+                            0 | 1 => {
+                                let element = random.next() % 1000;
+                                live.push((heap.push(element), element));
+                            }
+                            2 if !live.is_empty() => {
+                                let index = (random.next() as usize) % live.len();
+                                let element = live[index].1 / 2;
+                                let old = mem::replace(&mut live[index].1, element);
+                                assert_eq!(heap.promote(&live[index].0, element), Ok(old));
+                            }
+                            3 if !live.is_empty() => {
+                                let index = (random.next() as usize) % live.len();
+                                let (handle, element) = live.swap_remove(index);
+                                assert_eq!(heap.delete(&handle), Ok(element));
+                            }
+                            _ => {
+                                let smallest = live.iter().map(|&(_, element)| element).min();
+                                assert_eq!(heap.pop(), smallest);
+                                if smallest.is_some() {
+                                    let index = live
+                                        .iter()
+                                        .position(|(handle, _)| heap.get(handle).is_none())
+                                        .unwrap();
+                                    live.swap_remove(index);
+                                }
+                            }
+                        }
+                        assert_eq!(heap.len(), live.len());
+                    }
+                    let mut expected: Vec<_> = live.iter().map(|&(_, element)| element).collect();
+                    expected.sort_unstable();
+                    let mut iterated: Vec<_> = heap.iter().copied().collect();
+                    iterated.sort_unstable();
+                    assert_eq!(iterated, expected);
+                    assert_eq!(drain(&mut heap), expected);
+                }
+
+                #[test]
+                fn drops_every_element() {
+                    let drops = Rc::new(Cell::new(0));
+                    struct Counted(u32, Rc<Cell<u32>>);
+                    impl Drop for Counted {
+                        fn drop(&mut self) {
+                            self.1.set(self.1.get() + 1);
+                        }
+                    }
+                    let mut heap = $heap::with_comparator(|a: &Counted, b: &Counted| b.0.cmp(&a.0));
+                    let handles: Vec<_> = (0..100)
+                        .map(|i| heap.push(Counted(i, drops.clone())))
+                        .collect();
+                    heap.pop();
+                    heap.pop();
+                    assert_eq!(drops.get(), 2);
+                    drop(heap);
+                    assert_eq!(drops.get(), 100);
+                    assert!(handles
+                        .iter()
+                        .all(|handle| format!("{:?}", handle).contains("false")));
+                }
+
+                #[test]
+                fn deep_heap_drops_without_recursion() {
+                    on_small_stack(|| {
+                        let mut heap = $heap::with_comparator(Reversed);
+                        for element in (0..DEEP).rev() {
+                            heap.push(element);
+                        }
+                        assert_eq!(heap.pop(), Some(0));
+                        heap.clear();
+                        assert!(heap.is_empty());
+                        assert_eq!(heap.peek(), None);
+                        heap.extend((0..DEEP).rev());
+                    });
+                }
+            }
+        };
+    }
+
+    handle_tests!(pairing, PairingHeap);
+    handle_tests!(fibonacci, FibonacciHeap);
+
+    #[test]
+    fn debug() {
+        let heap: PairingHeap<i32> = vec![2].into_iter().collect();
+        assert_eq!(format!("{:?}", heap), "[2]");
+        let heap: FibonacciHeap<i32> = FibonacciHeap::new();
+        assert_eq!(format!("{:?}", heap), "[]");
+    }
+}
@@ -0,0 +1,643 @@
+use crate::error::DestructError;
+use crate::hashmap::HashMap;
+use crate::vector_based::VectorBasedDataStructure;
+use std::cmp::Ordering;
+use std::fmt::{self, Debug};
+use std::hash::Hash;
+use std::iter::FromIterator;
+use std::mem;
+use std::ops::{Deref, DerefMut};
+use std::slice;
+
+/// Operations shared by every heap in the crate. `pop` always removes the element that
+/// comes first in the queue's order, which `peek` shows without removing it.
+///
+/// The heaps that take a `Comparator` all default to `Natural` and pop the greatest
+/// element first, so they can replace each other without changing the order.
+/// `IndexedPriorityQueue` is the exception: it always pops the lowest priority.
+pub trait PriorityQueue<T> {
+    fn push(&mut self, element: T);
+    fn pop(&mut self) -> Option<T>;
+    fn peek(&self) -> Option<&T>;
+    fn len(&self) -> usize;
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Decides the order of a `BinaryHeap`, which pops the greatest element first.
+pub trait Comparator<T> {
+    fn compare(&self, a: &T, b: &T) -> Ordering;
+}
+
+/// Orders by `Ord`, making the heap a max-heap.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Natural;
+
+/// Orders by reversed `Ord`, making the heap a min-heap.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Reversed;
+
+impl<T: Ord> Comparator<T> for Natural {
+    fn compare(&self, a: &T, b: &T) -> Ordering {
+        a.cmp(b)
+    }
+}
+
+impl<T: Ord> Comparator<T> for Reversed {
+    fn compare(&self, a: &T, b: &T) -> Ordering {
+        b.cmp(a)
+    }
+}
+
+impl<T, F: Fn(&T, &T) -> Ordering> Comparator<T> for F {
+    fn compare(&self, a: &T, b: &T) -> Ordering {
+        self(a, b)
+    }
+}
+
+/// Binary heap stored in a `Vec`. It pops the greatest element according to `C`, so it
+/// is a max-heap by default and a min-heap with `Reversed` or `std::cmp::Reverse`.
+#[derive(Clone)]
+pub struct BinaryHeap<T, C = Natural> {
+    data: Vec<T>,
+    comparator: C,
+}
+
+impl<T: Ord> BinaryHeap<T> {
+    /// Builds a heap out of `vec` in O(n).
+    pub fn heapify(vec: Vec<T>) -> Self {
+        Self::heapify_with_comparator(vec, Natural)
+    }
+}
+
+impl<T, C: Comparator<T>> BinaryHeap<T, C> {
+    pub fn with_comparator(comparator: C) -> Self {
+        BinaryHeap {
+            data: Vec::new(),
+            comparator,
+        }
+    }
+
+    /// Builds a heap out of `vec` in O(n) by sifting down every parent, starting from
+    /// the last one.
+    pub fn heapify_with_comparator(vec: Vec<T>, comparator: C) -> Self {
+        let mut heap = BinaryHeap {
+            data: vec,
+            comparator,
+        };
+        for index in (0..heap.data.len() / 2).rev() {
+            heap.sift_down(index, heap.data.len());
+        }
+        heap
+    }
+
+    pub fn push(&mut self, element: T) {
+        self.data.push(element);
+        self.sift_up(self.data.len() - 1);
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        let last = self.data.pop()?;
+        if self.data.is_empty() {
+            return Some(last);
+        }
+        let top = mem::replace(&mut self.data[0], last);
+        self.sift_down(0, self.data.len());
+        Some(top)
+    }
+
+    pub fn peek(&self) -> Option<&T> {
+        self.data.first()
+    }
+
+    /// Gives mutable access to the top element. The heap is repaired when the returned
+    /// guard is dropped.
+    pub fn peek_mut(&mut self) -> Option<PeekMut<'_, T, C>> {
+        if self.data.is_empty() {
+            None
+        } else {
+            Some(PeekMut { heap: self })
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.data.clear();
+    }
+
+    /// Iterates in no particular order.
+    pub fn iter(&self) -> slice::Iter<'_, T> {
+        self.data.iter()
+    }
+
+    pub fn into_vec(self) -> Vec<T> {
+        self.data
+    }
+
+    /// Consumes the heap into a `Vec` sorted in ascending order of `C`.
+    pub fn into_sorted_vec(mut self) -> Vec<T> {
+        let mut end = self.data.len();
+        while end > 1 {
+            end -= 1;
+            self.data.swap(0, end);
+            self.sift_down(0, end);
+        }
+        self.data
+    }
+
+    fn sift_up(&mut self, mut index: usize) {
+        while index > 0 {
+            let parent = (index - 1) / 2;
+            if self.greater(index, parent) {
+                self.data.swap(index, parent);
+                index = parent;
+            } else {
+                break;
+            }
+        }
+    }
+
+    // Only the first `end` elements are treated as part of the heap
+    fn sift_down(&mut self, mut index: usize, end: usize) {
+        loop {
+            let mut largest = index;
+            for child in [2 * index + 1, 2 * index + 2] {
+                if child < end && self.greater(child, largest) {
+                    largest = child;
+                }
+            }
+            if largest == index {
+                break;
+            }
+            self.data.swap(index, largest);
+            index = largest;
+        }
+    }
+
+    fn greater(&self, a: usize, b: usize) -> bool {
+        self.comparator.compare(&self.data[a], &self.data[b]) == Ordering::Greater
+    }
+}
+
+impl<T, C: Comparator<T>> PriorityQueue<T> for BinaryHeap<T, C> {
+    fn push(&mut self, element: T) {
+        self.push(element);
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        self.pop()
+    }
+
+    fn peek(&self) -> Option<&T> {
+        self.peek()
+    }
+
+    fn len(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<T, C: Comparator<T> + Default> VectorBasedDataStructure<T> for BinaryHeap<T, C> {
+    fn new() -> Self {
+        Self::with_comparator(C::default())
+    }
+
+    fn with_capacity(capacity: usize) -> Self {
+        BinaryHeap {
+            data: Vec::with_capacity(capacity),
+            comparator: C::default(),
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    fn capacity(&self) -> usize {
+        self.data.capacity()
+    }
+}
+
+impl<T, C: Comparator<T> + Default> Default for BinaryHeap<T, C> {
+    fn default() -> Self {
+        Self::with_comparator(C::default())
+    }
+}
+
+impl<T: Debug, C> Debug for BinaryHeap<T, C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.data.iter()).finish()
+    }
+}
+
+impl<T, C: Comparator<T> + Default> From<Vec<T>> for BinaryHeap<T, C> {
+    fn from(vec: Vec<T>) -> Self {
+        Self::heapify_with_comparator(vec, C::default())
+    }
+}
+
+impl<T, C: Comparator<T> + Default> FromIterator<T> for BinaryHeap<T, C> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self::from(iter.into_iter().collect::<Vec<T>>())
+    }
+}
+
+impl<T, C: Comparator<T>> Extend<T> for BinaryHeap<T, C> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for element in iter {
+            self.push(element);
+        }
+    }
+}
+
+impl<'a, T, C> IntoIterator for &'a BinaryHeap<T, C> {
+    type Item = &'a T;
+    type IntoIter = slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.data.iter()
+    }
+}
+
+pub struct PeekMut<'a, T, C: Comparator<T>> {
+    heap: &'a mut BinaryHeap<T, C>,
+}
+
+impl<'a, T, C: Comparator<T>> PeekMut<'a, T, C> {
+    /// Removes the peeked element from the heap.
+    pub fn pop(this: PeekMut<'a, T, C>) -> T {
+        // The heap stays valid after `pop`, so the sift in `drop` has nothing to do
+        this.heap.pop().unwrap()
+    }
+}
+
+impl<'a, T, C: Comparator<T>> Deref for PeekMut<'a, T, C> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.heap.data[0]
+    }
+}
+
+impl<'a, T, C: Comparator<T>> DerefMut for PeekMut<'a, T, C> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.heap.data[0]
+    }
+}
+
+impl<'a, T, C: Comparator<T>> Drop for PeekMut<'a, T, C> {
+    fn drop(&mut self) {
+        let len = self.heap.data.len();
+        self.heap.sift_down(0, len);
+    }
+}
+
+/// Min-priority queue of unique keys whose priorities can be changed in O(log n), as
+/// needed by Dijkstra-style shortest-path searches. `pop` returns the lowest priority.
+#[derive(Clone)]
+pub struct IndexedPriorityQueue<K: Hash + PartialEq, P> {
+    heap: Vec<(K, P)>,
+    positions: HashMap<K, usize>,
+}
+
+impl<K: Hash + Eq + Clone, P: Ord> IndexedPriorityQueue<K, P> {
+    pub fn new() -> Self {
+        IndexedPriorityQueue {
+            heap: Vec::new(),
+            positions: HashMap::new(),
+        }
+    }
+
+    /// Adds `key` with `priority`. If the key is already queued, its priority is
+    /// replaced and the old one is returned.
+    pub fn push(&mut self, key: K, priority: P) -> Option<P> {
+        if let Some(&index) = self.positions.get(&key) {
+            let old = mem::replace(&mut self.heap[index].1, priority);
+            self.repair(index);
+            return Some(old);
+        }
+        self.positions.put(key.clone(), self.heap.len());
+        self.heap.push((key, priority));
+        self.sift_up(self.heap.len() - 1);
+        None
+    }
+
+    /// Removes the key with the lowest priority.
+    pub fn pop(&mut self) -> Option<(K, P)> {
+        if self.heap.is_empty() {
+            None
+        } else {
+            Some(self.remove_at(0))
+        }
+    }
+
+    pub fn peek(&self) -> Option<(&K, &P)> {
+        self.heap.first().map(|(key, priority)| (key, priority))
+    }
+
+    pub fn priority(&self, key: &K) -> Option<&P> {
+        self.positions.get(key).map(|&index| &self.heap[index].1)
+    }
+
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.positions.contains_key(key)
+    }
+
+    /// Lowers the priority of `key`, returning the previous one. Fails if the key is
+    /// missing or `priority` is higher than the current one.
+    pub fn decrease_key(&mut self, key: &K, priority: P) -> Result<P, DestructError> {
+        self.change_priority(key, priority, Ordering::Less)
+    }
+
+    /// Raises the priority of `key`, returning the previous one. Fails if the key is
+    /// missing or `priority` is lower than the current one.
+    pub fn increase_key(&mut self, key: &K, priority: P) -> Result<P, DestructError> {
+        self.change_priority(key, priority, Ordering::Greater)
+    }
+
+    /// Removes `key` from the queue, returning its priority.
+    pub fn remove(&mut self, key: &K) -> Option<P> {
+        let index = *self.positions.get(key)?;
+        Some(self.remove_at(index).1)
+    }
+
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.heap.clear();
+        self.positions.clear();
+    }
+
+    fn change_priority(
+        &mut self,
+        key: &K,
+        priority: P,
+        direction: Ordering,
+    ) -> Result<P, DestructError> {
+        let index = *self
+            .positions
+            .get(key)
+            .ok_or(DestructError::ElementNotFound)?;
+        if priority.cmp(&self.heap[index].1) == direction.reverse() {
+            return Err(DestructError::InvalidPriority);
+        }
+        let old = mem::replace(&mut self.heap[index].1, priority);
+        self.repair(index);
+        Ok(old)
+    }
+
+    fn remove_at(&mut self, index: usize) -> (K, P) {
+        let last = self.heap.len() - 1;
+        self.swap(index, last);
+        let (key, priority) = self.heap.pop().unwrap();
+        self.positions.remove(&key);
+        if index < self.heap.len() {
+            self.repair(index);
+        }
+        (key, priority)
+    }
+
+    // Moves the entry at `index` up or down, whichever its new priority requires
+    fn repair(&mut self, index: usize) {
+        let index = self.sift_up(index);
+        self.sift_down(index);
+    }
+
+    fn sift_up(&mut self, mut index: usize) -> usize {
+        while index > 0 {
+            let parent = (index - 1) / 2;
+            if self.heap[index].1 < self.heap[parent].1 {
+                self.swap(index, parent);
+                index = parent;
+            } else {
+                break;
+            }
+        }
+        index
+    }
+
+    fn sift_down(&mut self, mut index: usize) {
+        loop {
+            let mut smallest = index;
+            for child in [2 * index + 1, 2 * index + 2] {
+                if child < self.heap.len() && self.heap[child].1 < self.heap[smallest].1 {
+                    smallest = child;
+                }
+            }
+            if smallest == index {
+                break;
+            }
+            self.swap(index, smallest);
+            index = smallest;
+        }
+    }
+
+    fn swap(&mut self, a: usize, b: usize) {
+        self.heap.swap(a, b);
+        *self.positions.get_mut(&self.heap[a].0).unwrap() = a;
+        *self.positions.get_mut(&self.heap[b].0).unwrap() = b;
+    }
+}
+
+impl<K: Hash + Eq + Clone, P: Ord> Default for IndexedPriorityQueue<K, P> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Hash + Eq + Clone + Debug, P: Ord + Debug> Debug for IndexedPriorityQueue<K, P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map()
+            .entries(self.heap.iter().map(|(key, priority)| (key, priority)))
+            .finish()
+    }
+}
+
+/// Queues `(key, priority)` pairs. Pushing a key that is already queued updates its
+/// priority instead of adding a second entry.
+impl<K: Hash + Eq + Clone, P: Ord> PriorityQueue<(K, P)> for IndexedPriorityQueue<K, P> {
+    fn push(&mut self, (key, priority): (K, P)) {
+        self.push(key, priority);
+    }
+
+    fn pop(&mut self) -> Option<(K, P)> {
+        self.pop()
+    }
+
+    fn peek(&self) -> Option<&(K, P)> {
+        self.heap.first()
+    }
+
+    fn len(&self) -> usize {
+        self.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::DestructError;
+    use crate::priority_queue::{
+        BinaryHeap, IndexedPriorityQueue, PeekMut, PriorityQueue, Reversed,
+    };
+    use std::cmp::Reverse;
+
+    fn drain<T, Q: PriorityQueue<T>>(queue: &mut Q) -> Vec<T> {
+        let mut result = Vec::new();
+        while let Some(element) = queue.pop() {
+            result.push(element);
+        }
+        result
+    }
+
+    #[test]
+    fn max_heap_by_default() {
+        let mut heap = BinaryHeap::heapify(vec![]);
+        for element in [5, 1, 8, 3, 9, 2, 8] {
+            heap.push(element);
+        }
+        assert_eq!(heap.len(), 7);
+        assert_eq!(heap.peek(), Some(&9));
+        assert_eq!(drain(&mut heap), vec![9, 8, 8, 5, 3, 2, 1]);
+        assert!(heap.is_empty());
+        assert_eq!(heap.peek(), None);
+    }
+
+    #[test]
+    fn min_heap_through_reverse_or_comparator() {
+        let mut wrapped: BinaryHeap<_> = vec![4, 1, 3].into_iter().map(Reverse).collect();
+        assert_eq!(wrapped.pop(), Some(Reverse(1)));
+
+        let mut reversed: BinaryHeap<i32, Reversed> = vec![4, 1, 3, 2].into();
+        assert_eq!(drain(&mut reversed), vec![1, 2, 3, 4]);
+
+        let mut by_length = BinaryHeap::with_comparator(|a: &&str, b: &&str| b.len().cmp(&a.len()));
+        by_length.extend(["ccc", "a", "bb"]);
+        assert_eq!(drain(&mut by_length), vec!["a", "bb", "ccc"]);
+    }
+
+    #[test]
+    fn heapify_and_sorted_vec() {
+        let elements: Vec<u32> = (0..100).map(|i| (i * 37) % 101).collect();
+        let mut expected = elements.clone();
+        expected.sort_unstable();
+
+        let heap = BinaryHeap::heapify(elements.clone());
+        assert_eq!(heap.len(), 100);
+        assert_eq!(heap.peek(), expected.last());
+        assert_eq!(heap.into_sorted_vec(), expected);
+
+        let heap: BinaryHeap<u32, Reversed> =
+            BinaryHeap::heapify_with_comparator(elements, Reversed);
+        expected.reverse();
+        assert_eq!(heap.into_sorted_vec(), expected);
+    }
+
+    #[test]
+    fn peek_mut_restores_order() {
+        let mut heap = BinaryHeap::heapify(vec![3, 7, 5]);
+        *heap.peek_mut().unwrap() = 1;
+        assert_eq!(heap.peek(), Some(&5));
+
+        let top = PeekMut::pop(heap.peek_mut().unwrap());
+        assert_eq!(top, 5);
+        assert_eq!(drain(&mut heap), vec![3, 1]);
+        assert!(heap.peek_mut().is_none());
+    }
+
+    #[test]
+    fn indexed_queue_changes_priorities() {
+        let mut queue = IndexedPriorityQueue::new();
+        assert_eq!(queue.push("a", 5), None);
+        assert_eq!(queue.push("b", 3), None);
+        assert_eq!(queue.push("c", 8), None);
+        assert_eq!(queue.peek(), Some((&"b", &3)));
+
+        assert_eq!(queue.decrease_key(&"c", 1), Ok(8));
+        assert_eq!(queue.peek(), Some((&"c", &1)));
+        assert_eq!(queue.increase_key(&"c", 10), Ok(1));
+        assert_eq!(
+            queue.decrease_key(&"a", 7),
+            Err(DestructError::InvalidPriority)
+        );
+        assert_eq!(
+            queue.increase_key(&"a", 2),
+            Err(DestructError::InvalidPriority)
+        );
+        assert_eq!(
+            queue.decrease_key(&"z", 0),
+            Err(DestructError::ElementNotFound)
+        );
+        assert_eq!(queue.push("a", 4), Some(5));
+        assert_eq!(queue.priority(&"a"), Some(&4));
+
+        assert_eq!(queue.remove(&"b"), Some(3));
+        assert_eq!(queue.remove(&"b"), None);
+        assert!(!queue.contains_key(&"b"));
+        assert_eq!(queue.len(), 2);
+        assert_eq!(drain(&mut queue), vec![("a", 4), ("c", 10)]);
+    }
+
+    #[test]
+    fn indexed_queue_remove_keeps_heap_valid() {
+        let mut queue = IndexedPriorityQueue::new();
+        for key in 0..50u32 {
+            queue.push(key, (key * 17) % 53);
+        }
+        for key in (0..50).step_by(3) {
+            assert_eq!(queue.remove(&key), Some((key * 17) % 53));
+        }
+        let mut expected: Vec<(u32, u32)> = (0..50)
+            .filter(|key| key % 3 != 0)
+            .map(|key| (key, (key * 17) % 53))
+            .collect();
+        expected.sort_by_key(|&(_, priority)| priority);
+        assert_eq!(drain(&mut queue), expected);
+    }
+
+    #[test]
+    fn dijkstra_shortest_paths() {
+        let edges: [&[(usize, u32)]; 5] = [
+            &[(1, 4), (2, 1)],
+            &[(3, 1)],
+            &[(1, 2), (3, 5)],
+            &[(4, 3)],
+            &[],
+        ];
+        let mut distances = [u32::MAX; 5];
+        let mut queue = IndexedPriorityQueue::new();
+        distances[0] = 0;
+        queue.push(0, 0);
+        while let Some((node, distance)) = queue.pop() {
+            for &(next, weight) in edges[node] {
+                let candidate = distance + weight;
+                if candidate < distances[next] {
+                    distances[next] = candidate;
+                    if queue.contains_key(&next) {
+                        queue.decrease_key(&next, candidate).unwrap();
+                    } else {
+                        queue.push(next, candidate);
+                    }
+                }
+            }
+        }
+        assert_eq!(distances, [0, 3, 1, 4, 7]);
+    }
+}
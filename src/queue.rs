@@ -1,73 +1,327 @@
-use std::cell::RefCell;
-use std::rc::Rc;
+use crate::queue_like::QueueLike;
+use std::cmp::Ordering;
+use std::fmt::{self, Debug};
+use std::hash::{Hash, Hasher};
+use std::iter::FromIterator;
+use std::marker::PhantomData;
+use std::ptr::NonNull;
+
+/// Singly linked FIFO queue that keeps a raw pointer to its tail, so both `enqueue` and
+/// `dequeue` are O(1) without any reference counting.
+///
+/// Every unsafe block relies on the same invariant: `head`, `tail` and the `next` links
+/// only ever point to live nodes owned by this queue, and `tail` is the last of them.
+pub struct Queue<T> {
+    head: Option<NonNull<QueueNode<T>>>,
+    tail: Option<NonNull<QueueNode<T>>>,
+    len: usize,
+    marker: PhantomData<Box<QueueNode<T>>>,
+}
 
 struct QueueNode<T> {
     data: T,
-    next: Option<Rc<RefCell<QueueNode<T>>>>,
+    next: Option<NonNull<QueueNode<T>>>,
 }
 
-pub struct Queue<T> {
-    head: Option<Rc<RefCell<QueueNode<T>>>>,
-    tail: Option<Rc<RefCell<QueueNode<T>>>>,
-    len: usize,
+// SAFETY: the queue uniquely owns its nodes, so sending or sharing it is as safe as
+// sending or sharing the `T`s it holds
+unsafe impl<T: Send> Send for Queue<T> {}
+unsafe impl<T: Sync> Sync for Queue<T> {}
+
+impl<T> Default for Queue<T> {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl<T> Queue<T> {
     pub fn enqueue(&mut self, element: T) {
-        let new_tail = Rc::from(RefCell::new(QueueNode {
+        let new_tail = NonNull::from(Box::leak(Box::new(QueueNode {
             data: element,
             next: None,
-        }));
-        if let Some(current_tail) = &mut self.tail {
-            current_tail.borrow_mut().next.replace(new_tail.clone());
-            self.tail.replace(new_tail);
-        } else {
-            self.head.replace(new_tail.clone());
-            self.tail.replace(new_tail);
+        })));
+        match self.tail {
+            // SAFETY: `tail` points to a live node owned by this queue
+            Some(tail) => unsafe { (*tail.as_ptr()).next = Some(new_tail) },
+            None => self.head = Some(new_tail),
         }
+        self.tail = Some(new_tail);
         self.len += 1;
     }
 
     pub fn dequeue(&mut self) -> Option<T> {
-        return self.head.take().map(|head| {
-            if Rc::ptr_eq(&head, self.tail.as_ref().unwrap()) {
+        self.head.map(|head| {
+            // SAFETY: `head` was leaked from a `Box` in `enqueue` and is unlinked here
+            let head = unsafe { Box::from_raw(head.as_ptr()) };
+            self.head = head.next;
+            if self.head.is_none() {
                 self.tail = None;
             }
-            let head = Rc::try_unwrap(head)
-                .unwrap_or_else(|_| panic!("Failed to unwrap Rc to the head of the Queue"));
-            let inner_head = head.into_inner();
-            self.head = inner_head.next.clone();
             self.len -= 1;
-            return inner_head.data;
-        });
+            head.data
+        })
+    }
+
+    pub fn peek(&self) -> Option<&T> {
+        // SAFETY: the node lives as long as the queue is borrowed
+        self.head.map(|head| unsafe { &(*head.as_ptr()).data })
+    }
+
+    pub fn peek_mut(&mut self) -> Option<&mut T> {
+        // SAFETY: the node lives as long as the queue is mutably borrowed
+        self.head.map(|head| unsafe { &mut (*head.as_ptr()).data })
+    }
+
+    pub fn peek_back(&self) -> Option<&T> {
+        // SAFETY: the node lives as long as the queue is borrowed
+        self.tail.map(|tail| unsafe { &(*tail.as_ptr()).data })
+    }
+
+    pub fn clear(&mut self) {
+        while self.dequeue().is_some() {}
     }
 
     pub fn len(&self) -> usize {
         self.len
     }
 
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
     pub fn new() -> Self {
         Queue {
             head: None,
             tail: None,
             len: 0,
+            marker: PhantomData,
+        }
+    }
+
+    /// Iterates from the front of the queue to the back.
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            next: self.head,
+            len: self.len,
+            marker: PhantomData,
+        }
+    }
+
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut {
+            next: self.head,
+            len: self.len,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<T> QueueLike<T> for Queue<T> {
+    fn enqueue(&mut self, element: T) {
+        self.enqueue(element);
+    }
+
+    fn dequeue(&mut self) -> Option<T> {
+        self.dequeue()
+    }
+
+    fn peek(&self) -> Option<&T> {
+        self.peek()
+    }
+
+    fn peek_back(&self) -> Option<&T> {
+        self.peek_back()
+    }
+
+    fn len(&self) -> usize {
+        self.len()
+    }
+
+    fn clear(&mut self) {
+        self.clear();
+    }
+}
+
+// The nodes are raw allocations, so they have to be freed by hand
+impl<T> Drop for Queue<T> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+impl<T: Clone> Clone for Queue<T> {
+    fn clone(&self) -> Self {
+        self.iter().cloned().collect()
+    }
+}
+
+impl<T: Debug> Debug for Queue<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: PartialEq> PartialEq for Queue<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Eq> Eq for Queue<T> {}
+
+impl<T: PartialOrd> PartialOrd for Queue<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.iter().partial_cmp(other.iter())
+    }
+}
+
+impl<T: Ord> Ord for Queue<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.iter().cmp(other.iter())
+    }
+}
+
+impl<T: Hash> Hash for Queue<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.len.hash(state);
+        for element in self.iter() {
+            element.hash(state);
         }
     }
 }
 
 impl<T> From<Vec<T>> for Queue<T> {
     fn from(vec: Vec<T>) -> Self {
+        vec.into_iter().collect()
+    }
+}
+
+impl<T> FromIterator<T> for Queue<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
         let mut queue = Queue::new();
-        for element in vec {
-            queue.enqueue(element);
-        }
+        queue.extend(iter);
         queue
     }
 }
 
+impl<T> Extend<T> for Queue<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for element in iter {
+            self.enqueue(element);
+        }
+    }
+}
+
+impl<T> IntoIterator for Queue<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter(self)
+    }
+}
+
+impl<'a, T> IntoIterator for &'a Queue<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut Queue<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+pub struct IntoIter<T>(Queue<T>);
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.dequeue()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.0.len, Some(self.0.len))
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+pub struct Iter<'a, T> {
+    next: Option<NonNull<QueueNode<T>>>,
+    len: usize,
+    marker: PhantomData<&'a QueueNode<T>>,
+}
+
+// SAFETY: `Iter` only hands out `&T`, so it behaves like a `&Queue<T>`
+unsafe impl<T: Sync> Send for Iter<'_, T> {}
+unsafe impl<T: Sync> Sync for Iter<'_, T> {}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next.map(|node| {
+            // SAFETY: the queue is borrowed for 'a, so the node stays alive
+            let node = unsafe { &*node.as_ptr() };
+            self.next = node.next;
+            self.len -= 1;
+            &node.data
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, T> ExactSizeIterator for Iter<'a, T> {}
+
+pub struct IterMut<'a, T> {
+    next: Option<NonNull<QueueNode<T>>>,
+    len: usize,
+    marker: PhantomData<&'a mut QueueNode<T>>,
+}
+
+// SAFETY: `IterMut` hands out `&mut T` for nodes nothing else can reach while it lives,
+// so it behaves like a `&mut Queue<T>`
+unsafe impl<T: Send> Send for IterMut<'_, T> {}
+unsafe impl<T: Sync> Sync for IterMut<'_, T> {}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next.map(|node| {
+            // SAFETY: the queue is mutably borrowed for 'a and every node is yielded once
+            let node = unsafe { &mut *node.as_ptr() };
+            self.next = node.next;
+            self.len -= 1;
+            &mut node.data
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, T> ExactSizeIterator for IterMut<'a, T> {}
+
 #[cfg(test)]
 mod tests {
-    use crate::queue::Queue;
+    use crate::queue::{Iter, IterMut, Queue};
+    use crate::queue_like::queue_like_tests;
+
+    queue_like_tests!(queue_like, Queue<i32>);
 
     #[test]
     fn enqueue() {
@@ -109,4 +363,196 @@ mod tests {
         assert_eq!(queue.dequeue().unwrap(), 4);
         assert_eq!(queue.dequeue(), None);
     }
+
+    #[test]
+    fn into_iter() {
+        let queue = Queue::from(vec![1, 2, 3]);
+        let mut iter = queue.into_iter();
+        assert_eq!(iter.len(), 3);
+        assert_eq!(iter.next(), Some(1));
+        assert_eq!(iter.collect::<Vec<_>>(), vec![2, 3]);
+    }
+
+    #[test]
+    fn from_iter_and_extend() {
+        let mut queue: Queue<i32> = (1..=3).collect();
+        queue.extend(vec![4, 5]);
+        assert_eq!(queue.len(), 5);
+        assert_eq!(queue.dequeue(), Some(1));
+    }
+
+    #[test]
+    fn std_traits() {
+        use std::collections::hash_map::DefaultHasher;
+        use std::hash::{Hash, Hasher};
+
+        let queue = Queue::from(vec![1, 2, 3]);
+        let mut copy = queue.clone();
+        assert_eq!(queue, copy);
+        assert_eq!(copy.dequeue(), Some(1));
+        assert_ne!(queue, copy);
+        // The clone doesn't share nodes with the original
+        assert_eq!(format!("{:?}", queue), "[1, 2, 3]");
+        assert_eq!(format!("{:?}", copy), "[2, 3]");
+        assert_eq!(Queue::<i32>::default(), Queue::new());
+        assert!(Queue::from(vec![1, 2]) < Queue::from(vec![1, 3]));
+        assert!(Queue::from(vec![1, 2]) < Queue::from(vec![1, 2, 0]));
+        assert!(queue < copy);
+        let hash = |queue: &Queue<i32>| {
+            let mut hasher = DefaultHasher::new();
+            queue.hash(&mut hasher);
+            hasher.finish()
+        };
+        assert_eq!(hash(&queue), hash(&queue.clone()));
+        assert_ne!(hash(&queue), hash(&copy));
+    }
+
+    #[test]
+    fn peek() {
+        let mut queue = Queue::new();
+        assert_eq!(queue.peek(), None);
+        queue.enqueue("first".to_string());
+        queue.enqueue("second".to_string());
+        assert_eq!(queue.peek().unwrap(), "first");
+        assert_eq!(queue.peek_back().unwrap(), "second");
+        queue.dequeue();
+        assert_eq!(queue.peek(), queue.peek_back());
+    }
+
+    #[test]
+    fn peek_mut() {
+        let mut queue = Queue::from(vec![1, 2]);
+        *queue.peek_mut().unwrap() = 10;
+        assert_eq!(queue.dequeue(), Some(10));
+        assert_eq!(queue.peek_mut(), Some(&mut 2));
+        queue.dequeue();
+        assert_eq!(queue.peek_mut(), None);
+    }
+
+    #[test]
+    fn iter() {
+        let queue = Queue::from(vec![1, 2, 3]);
+        let mut iter = queue.iter();
+        assert_eq!(iter.len(), 3);
+        assert_eq!(iter.next(), Some(&1));
+        assert_eq!(iter.collect::<Vec<_>>(), vec![&2, &3]);
+        assert_eq!((&queue).into_iter().count(), 3);
+        assert_eq!(Queue::<i32>::new().iter().next(), None);
+    }
+
+    #[test]
+    fn iter_mut() {
+        let mut queue = Queue::from(vec![1, 2, 3]);
+        for element in queue.iter_mut() {
+            *element *= 2;
+        }
+        for element in &mut queue {
+            *element += 1;
+        }
+        assert_eq!(queue, Queue::from(vec![3, 5, 7]));
+        // Enqueueing after iterating still links to the right tail
+        queue.enqueue(9);
+        assert_eq!(queue.iter().copied().collect::<Vec<_>>(), vec![3, 5, 7, 9]);
+    }
+
+    #[test]
+    fn iterators_are_send_and_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<Queue<String>>();
+        assert_send_sync::<Iter<'_, String>>();
+        assert_send_sync::<IterMut<'_, String>>();
+    }
+
+    // The previous design, kept here as the baseline for the benchmark below
+    mod rc_queue {
+        use std::cell::RefCell;
+        use std::rc::Rc;
+
+        struct Node<T> {
+            data: T,
+            next: Option<Rc<RefCell<Node<T>>>>,
+        }
+
+        pub struct RcQueue<T> {
+            head: Option<Rc<RefCell<Node<T>>>>,
+            tail: Option<Rc<RefCell<Node<T>>>>,
+        }
+
+        impl<T> RcQueue<T> {
+            pub fn new() -> Self {
+                RcQueue {
+                    head: None,
+                    tail: None,
+                }
+            }
+
+            pub fn enqueue(&mut self, element: T) {
+                let new_tail = Rc::new(RefCell::new(Node {
+                    data: element,
+                    next: None,
+                }));
+                match self.tail.take() {
+                    Some(tail) => tail.borrow_mut().next = Some(new_tail.clone()),
+                    None => self.head = Some(new_tail.clone()),
+                }
+                self.tail = Some(new_tail);
+            }
+
+            pub fn dequeue(&mut self) -> Option<T> {
+                self.head.take().map(|head| {
+                    if Rc::ptr_eq(&head, self.tail.as_ref().unwrap()) {
+                        self.tail = None;
+                    }
+                    let node = Rc::try_unwrap(head).ok().unwrap().into_inner();
+                    self.head = node.next;
+                    node.data
+                })
+            }
+        }
+    }
+
+    // Timing based, so it only runs on request:
+    // `cargo test --release -- --ignored bench_against_rc_queue`
+    #[test]
+    #[ignore]
+    fn bench_against_rc_queue() {
+        use std::time::Instant;
+
+        const ELEMENTS: u64 = 1_000_000;
+        const ROUNDS: usize = 10;
+
+        let start = Instant::now();
+        let mut sum = 0;
+        for _ in 0..ROUNDS {
+            let mut queue = Queue::new();
+            for i in 0..ELEMENTS {
+                queue.enqueue(i);
+            }
+            while let Some(element) = queue.dequeue() {
+                sum += element;
+            }
+        }
+        let queue_time = start.elapsed();
+
+        let start = Instant::now();
+        let mut rc_sum = 0;
+        for _ in 0..ROUNDS {
+            let mut queue = rc_queue::RcQueue::new();
+            for i in 0..ELEMENTS {
+                queue.enqueue(i);
+            }
+            while let Some(element) = queue.dequeue() {
+                rc_sum += element;
+            }
+        }
+        let rc_time = start.elapsed();
+
+        assert_eq!(sum, rc_sum);
+        assert!(
+            rc_time > queue_time,
+            "Queue took {:?} but the Rc<RefCell> queue only {:?}",
+            queue_time,
+            rc_time
+        );
+    }
 }
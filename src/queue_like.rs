@@ -0,0 +1,126 @@
+/// FIFO operations shared by every queue in the crate.
+pub trait QueueLike<T> {
+    fn enqueue(&mut self, element: T);
+    fn dequeue(&mut self) -> Option<T>;
+    /// The element that `dequeue` would return next.
+    fn peek(&self) -> Option<&T>;
+    /// The most recently enqueued element.
+    fn peek_back(&self) -> Option<&T>;
+    fn len(&self) -> usize;
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    fn clear(&mut self);
+}
+
+/// Generates the `QueueLike` conformance suite as a module named `$name`, run against
+/// `$queue`, which must hold `i32` and implement `Default`. Implementations opt in by
+/// invoking it from their own test module.
+#[cfg(test)]
+macro_rules! queue_like_tests {
+    ($name:ident, $queue:ty) => {
+        mod $name {
+            use super::*;
+            use crate::queue_like::QueueLike;
+
+            fn new_queue() -> $queue {
+                <$queue>::default()
+            }
+
+            fn drain<Q: QueueLike<i32>>(queue: &mut Q) -> Vec<i32> {
+                let mut dequeued = Vec::new();
+                while let Some(element) = queue.dequeue() {
+                    dequeued.push(element);
+                }
+                dequeued
+            }
+
+            #[test]
+            fn empty() {
+                let mut queue = new_queue();
+                assert!(QueueLike::is_empty(&queue));
+                assert_eq!(QueueLike::len(&queue), 0);
+                assert_eq!(QueueLike::peek(&queue), None);
+                assert_eq!(QueueLike::peek_back(&queue), None);
+                assert_eq!(QueueLike::dequeue(&mut queue), None);
+            }
+
+            #[test]
+            fn first_in_first_out() {
+                let mut queue = new_queue();
+                for i in 0..5 {
+                    QueueLike::enqueue(&mut queue, i);
+                    assert_eq!(QueueLike::peek(&queue), Some(&0));
+                    assert_eq!(QueueLike::peek_back(&queue), Some(&i));
+                    assert_eq!(QueueLike::len(&queue), i as usize + 1);
+                }
+                assert_eq!(drain(&mut queue), vec![0, 1, 2, 3, 4]);
+                assert!(QueueLike::is_empty(&queue));
+            }
+
+            #[test]
+            fn interleaved_enqueue_and_dequeue() {
+                let mut queue = new_queue();
+                QueueLike::enqueue(&mut queue, 1);
+                QueueLike::enqueue(&mut queue, 2);
+                assert_eq!(QueueLike::dequeue(&mut queue), Some(1));
+                QueueLike::enqueue(&mut queue, 3);
+                assert_eq!(QueueLike::peek(&queue), Some(&2));
+                assert_eq!(QueueLike::dequeue(&mut queue), Some(2));
+                assert_eq!(QueueLike::dequeue(&mut queue), Some(3));
+                assert_eq!(QueueLike::peek_back(&queue), None);
+                // An emptied queue must accept new elements at both ends again
+                QueueLike::enqueue(&mut queue, 4);
+                assert_eq!(QueueLike::peek(&queue), Some(&4));
+                assert_eq!(QueueLike::peek_back(&queue), Some(&4));
+                assert_eq!(QueueLike::len(&queue), 1);
+            }
+
+            #[test]
+            fn single_element() {
+                let mut queue = new_queue();
+                QueueLike::enqueue(&mut queue, 7);
+                assert_eq!(QueueLike::peek(&queue), QueueLike::peek_back(&queue));
+                assert_eq!(QueueLike::dequeue(&mut queue), Some(7));
+                assert!(QueueLike::is_empty(&queue));
+            }
+
+            #[test]
+            fn clear() {
+                let mut queue = new_queue();
+                for i in 0..100 {
+                    QueueLike::enqueue(&mut queue, i);
+                }
+                QueueLike::clear(&mut queue);
+                assert!(QueueLike::is_empty(&queue));
+                assert_eq!(QueueLike::peek(&queue), None);
+                assert_eq!(QueueLike::peek_back(&queue), None);
+                QueueLike::enqueue(&mut queue, 7);
+                assert_eq!(drain(&mut queue), vec![7]);
+            }
+
+            #[test]
+            fn many_elements() {
+                let mut queue = new_queue();
+                for i in 0..10_000 {
+                    QueueLike::enqueue(&mut queue, i);
+                }
+                assert_eq!(QueueLike::len(&queue), 10_000);
+                assert_eq!(drain(&mut queue), (0..10_000).collect::<Vec<_>>());
+            }
+
+            #[test]
+            fn drop_long_queue() {
+                crate::test_util::on_small_stack(|| {
+                    let mut queue = new_queue();
+                    for i in 0..crate::test_util::DEEP {
+                        QueueLike::enqueue(&mut queue, i);
+                    }
+                });
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+pub(crate) use queue_like_tests;
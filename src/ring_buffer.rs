@@ -0,0 +1,453 @@
+use std::fmt::{self, Debug};
+use std::marker::PhantomData;
+
+/// Ring buffer with a capacity fixed at construction. Once full, `push_overwrite` drops
+/// the oldest element while `try_push` refuses the new one.
+#[derive(Clone)]
+pub struct RingBuffer<T> {
+    ring: Ring<T, Box<[Option<T>]>>,
+}
+
+/// Same as `RingBuffer`, but stores its `N` slots inline so it never allocates.
+#[derive(Clone)]
+pub struct ArrayRingBuffer<T, const N: usize> {
+    ring: Ring<T, [Option<T>; N]>,
+}
+
+// Storage-independent logic shared by both buffers. The elements occupy `len` slots
+// starting at `head` and wrapping around, and every other slot is `None`.
+#[derive(Clone)]
+struct Ring<T, S> {
+    slots: S,
+    head: usize,
+    len: usize,
+    marker: PhantomData<T>,
+}
+
+// Both buffers expose the same methods on top of their `Ring`, generated here so that
+// they can't drift apart
+macro_rules! ring_methods {
+    () => {
+        /// Appends `element`, dropping and returning the oldest element if the buffer is
+        /// full.
+        pub fn push_overwrite(&mut self, element: T) -> Option<T> {
+            self.ring.push_overwrite(element)
+        }
+
+        /// Appends `element`, or hands it back if the buffer is full.
+        pub fn try_push(&mut self, element: T) -> Result<(), T> {
+            self.ring.try_push(element)
+        }
+
+        /// Removes the oldest element.
+        pub fn pop(&mut self) -> Option<T> {
+            self.ring.pop()
+        }
+
+        pub fn oldest(&self) -> Option<&T> {
+            self.ring.get(0)
+        }
+
+        pub fn newest(&self) -> Option<&T> {
+            self.ring.get(self.ring.len.checked_sub(1)?)
+        }
+
+        pub fn len(&self) -> usize {
+            self.ring.len
+        }
+
+        pub fn is_empty(&self) -> bool {
+            self.ring.len == 0
+        }
+
+        pub fn is_full(&self) -> bool {
+            self.ring.is_full()
+        }
+
+        pub fn capacity(&self) -> usize {
+            self.ring.capacity()
+        }
+
+        pub fn clear(&mut self) {
+            self.ring.drain();
+        }
+
+        /// Iterates from the oldest element to the newest.
+        pub fn iter(&self) -> Iter<'_, T> {
+            self.ring.iter()
+        }
+
+        /// Removes every element, yielding them from the oldest to the newest.
+        pub fn drain(&mut self) -> Drain<'_, T> {
+            self.ring.drain()
+        }
+
+        /// Copies the elements, from the oldest to the newest, into a `Vec`.
+        pub fn snapshot(&self) -> Vec<T>
+        where
+            T: Clone,
+        {
+            self.iter().cloned().collect()
+        }
+    };
+}
+
+// The trait impls the two buffers share, for a buffer type and its generic parameters
+macro_rules! ring_impls {
+    ($buffer:ty, $($generics:tt)*) => {
+        impl<$($generics)*> Debug for $buffer
+        where
+            T: Debug,
+        {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.debug_list().entries(self.iter()).finish()
+            }
+        }
+
+        impl<'a, $($generics)*> IntoIterator for &'a $buffer {
+            type Item = &'a T;
+            type IntoIter = Iter<'a, T>;
+
+            fn into_iter(self) -> Self::IntoIter {
+                self.iter()
+            }
+        }
+    };
+}
+
+impl<T> RingBuffer<T> {
+    /// Panics if `capacity` is 0.
+    pub fn new(capacity: usize) -> Self {
+        assert!(capacity > 0, "RingBuffer capacity must be positive");
+        RingBuffer {
+            ring: Ring::new((0..capacity).map(|_| None).collect()),
+        }
+    }
+
+    ring_methods!();
+}
+
+impl<T, const N: usize> ArrayRingBuffer<T, N> {
+    /// Panics if `N` is 0.
+    pub fn new() -> Self {
+        assert!(N > 0, "ArrayRingBuffer capacity must be positive");
+        ArrayRingBuffer {
+            ring: Ring::new([(); N].map(|_| None)),
+        }
+    }
+
+    ring_methods!();
+}
+
+impl<T, const N: usize> Default for ArrayRingBuffer<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+ring_impls!(RingBuffer<T>, T);
+ring_impls!(ArrayRingBuffer<T, N>, T, const N: usize);
+
+impl<T, S> Ring<T, S>
+where
+    S: AsRef<[Option<T>]> + AsMut<[Option<T>]>,
+{
+    fn new(slots: S) -> Self {
+        Ring {
+            slots,
+            head: 0,
+            len: 0,
+            marker: PhantomData,
+        }
+    }
+
+    fn capacity(&self) -> usize {
+        self.slots.as_ref().len()
+    }
+
+    fn is_full(&self) -> bool {
+        self.len == self.capacity()
+    }
+
+    fn get(&self, index: usize) -> Option<&T> {
+        if index < self.len {
+            self.slots.as_ref()[self.physical(index)].as_ref()
+        } else {
+            None
+        }
+    }
+
+    fn push_overwrite(&mut self, element: T) -> Option<T> {
+        if self.is_full() {
+            let oldest = self.slots.as_mut()[self.head].replace(element);
+            self.head = self.physical(1);
+            oldest
+        } else {
+            let index = self.physical(self.len);
+            self.slots.as_mut()[index] = Some(element);
+            self.len += 1;
+            None
+        }
+    }
+
+    fn try_push(&mut self, element: T) -> Result<(), T> {
+        if self.is_full() {
+            Err(element)
+        } else {
+            self.push_overwrite(element);
+            Ok(())
+        }
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        if self.len == 0 {
+            return None;
+        }
+        let oldest = self.slots.as_mut()[self.head].take();
+        self.head = self.physical(1);
+        self.len -= 1;
+        oldest
+    }
+
+    fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            slots: self.slots.as_ref(),
+            head: self.head,
+            front: 0,
+            back: self.len,
+        }
+    }
+
+    // The ring is left empty right away; the drain takes the elements out of their slots
+    fn drain(&mut self) -> Drain<'_, T> {
+        let head = std::mem::replace(&mut self.head, 0);
+        let len = std::mem::replace(&mut self.len, 0);
+        Drain {
+            slots: self.slots.as_mut(),
+            head,
+            front: 0,
+            back: len,
+        }
+    }
+
+    fn physical(&self, index: usize) -> usize {
+        (self.head + index) % self.capacity()
+    }
+}
+
+pub struct Iter<'a, T> {
+    slots: &'a [Option<T>],
+    head: usize,
+    front: usize,
+    back: usize,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.front == self.back {
+            return None;
+        }
+        let index = (self.head + self.front) % self.slots.len();
+        self.front += 1;
+        self.slots[index].as_ref()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.back - self.front;
+        (len, Some(len))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for Iter<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.front == self.back {
+            return None;
+        }
+        self.back -= 1;
+        let index = (self.head + self.back) % self.slots.len();
+        self.slots[index].as_ref()
+    }
+}
+
+impl<'a, T> ExactSizeIterator for Iter<'a, T> {}
+
+/// Draining iterator. Elements it did not yield are dropped along with it.
+pub struct Drain<'a, T> {
+    slots: &'a mut [Option<T>],
+    head: usize,
+    front: usize,
+    back: usize,
+}
+
+impl<'a, T> Iterator for Drain<'a, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.front == self.back {
+            return None;
+        }
+        let index = (self.head + self.front) % self.slots.len();
+        self.front += 1;
+        self.slots[index].take()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.back - self.front;
+        (len, Some(len))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for Drain<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.front == self.back {
+            return None;
+        }
+        self.back -= 1;
+        let index = (self.head + self.back) % self.slots.len();
+        self.slots[index].take()
+    }
+}
+
+impl<'a, T> ExactSizeIterator for Drain<'a, T> {}
+
+impl<'a, T> Drop for Drain<'a, T> {
+    fn drop(&mut self) {
+        self.for_each(drop);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ring_buffer::{ArrayRingBuffer, RingBuffer};
+    use std::rc::Rc;
+
+    #[test]
+    fn push_overwrite() {
+        let mut buffer = RingBuffer::new(3);
+        assert_eq!(buffer.push_overwrite(1), None);
+        assert_eq!(buffer.push_overwrite(2), None);
+        assert_eq!(buffer.push_overwrite(3), None);
+        assert!(buffer.is_full());
+        assert_eq!(buffer.push_overwrite(4), Some(1));
+        assert_eq!(buffer.push_overwrite(5), Some(2));
+        assert_eq!(buffer.len(), 3);
+        assert_eq!(buffer.snapshot(), vec![3, 4, 5]);
+        assert_eq!(buffer.oldest(), Some(&3));
+        assert_eq!(buffer.newest(), Some(&5));
+    }
+
+    #[test]
+    fn try_push() {
+        let mut buffer = RingBuffer::new(2);
+        assert_eq!(buffer.try_push("a"), Ok(()));
+        assert_eq!(buffer.try_push("b"), Ok(()));
+        assert_eq!(buffer.try_push("c"), Err("c"));
+        assert_eq!(buffer.pop(), Some("a"));
+        assert_eq!(buffer.try_push("c"), Ok(()));
+        assert_eq!(buffer.snapshot(), vec!["b", "c"]);
+    }
+
+    #[test]
+    #[should_panic(expected = "RingBuffer capacity must be positive")]
+    fn zero_capacity() {
+        RingBuffer::<i32>::new(0);
+    }
+
+    #[test]
+    fn iter_oldest_to_newest() {
+        let mut buffer = RingBuffer::new(4);
+        for i in 0..10 {
+            buffer.push_overwrite(i);
+        }
+        let mut iter = buffer.iter();
+        assert_eq!(iter.len(), 4);
+        assert_eq!(iter.next(), Some(&6));
+        assert_eq!(iter.next_back(), Some(&9));
+        assert_eq!(iter.collect::<Vec<_>>(), vec![&7, &8]);
+        assert_eq!((&buffer).into_iter().rev().count(), 4);
+        assert_eq!(format!("{:?}", buffer), "[6, 7, 8, 9]");
+    }
+
+    #[test]
+    fn drain() {
+        let mut buffer = RingBuffer::new(3);
+        for i in 0..5 {
+            buffer.push_overwrite(i);
+        }
+        assert_eq!(buffer.drain().collect::<Vec<_>>(), vec![2, 3, 4]);
+        assert!(buffer.is_empty());
+        assert_eq!(buffer.oldest(), None);
+        buffer.push_overwrite(7);
+        assert_eq!(buffer.snapshot(), vec![7]);
+    }
+
+    #[test]
+    fn drain_dropped_early() {
+        let counter = Rc::new(());
+        let mut buffer = RingBuffer::new(3);
+        for _ in 0..3 {
+            buffer.push_overwrite(Rc::clone(&counter));
+        }
+        assert_eq!(buffer.drain().take(1).count(), 1);
+        assert!(buffer.is_empty());
+        assert_eq!(Rc::strong_count(&counter), 1);
+    }
+
+    #[test]
+    fn clear_and_clone() {
+        let mut buffer = RingBuffer::new(2);
+        buffer.push_overwrite(1);
+        buffer.push_overwrite(2);
+        buffer.push_overwrite(3);
+        let copy = buffer.clone();
+        buffer.clear();
+        assert!(buffer.is_empty());
+        assert_eq!(buffer.capacity(), 2);
+        assert_eq!(copy.snapshot(), vec![2, 3]);
+    }
+
+    #[test]
+    fn array_ring_buffer() {
+        let mut buffer: ArrayRingBuffer<i32, 3> = ArrayRingBuffer::new();
+        assert_eq!(buffer.capacity(), 3);
+        assert!(buffer.is_empty());
+        assert_eq!(buffer.try_push(1), Ok(()));
+        assert_eq!(buffer.push_overwrite(2), None);
+        assert_eq!(buffer.push_overwrite(3), None);
+        assert_eq!(buffer.try_push(4), Err(4));
+        assert_eq!(buffer.push_overwrite(4), Some(1));
+        assert_eq!(buffer.snapshot(), vec![2, 3, 4]);
+        assert_eq!(buffer.newest(), Some(&4));
+        assert_eq!(buffer.pop(), Some(2));
+        assert_eq!(buffer.len(), 2);
+        assert_eq!(format!("{:?}", buffer), "[3, 4]");
+        assert_eq!((&buffer).into_iter().count(), 2);
+        let copy = buffer.clone();
+        assert_eq!(buffer.drain().rev().collect::<Vec<_>>(), vec![4, 3]);
+        assert!(buffer.is_empty());
+        assert_eq!(copy.snapshot(), vec![3, 4]);
+        buffer.clear();
+        assert_eq!(ArrayRingBuffer::<i32, 2>::default().len(), 0);
+    }
+
+    #[test]
+    fn array_ring_buffer_stays_inline() {
+        // The slots live in the struct itself, so its size grows with `N`
+        assert!(
+            std::mem::size_of::<ArrayRingBuffer<u64, 64>>()
+                >= 64 * std::mem::size_of::<Option<u64>>()
+        );
+        assert_eq!(
+            std::mem::size_of::<RingBuffer<u64>>(),
+            std::mem::size_of::<RingBuffer<[u64; 64]>>()
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "ArrayRingBuffer capacity must be positive")]
+    fn array_zero_capacity() {
+        ArrayRingBuffer::<i32, 0>::new();
+    }
+}
@@ -1,5 +1,12 @@
+use crate::stack_like::StackLike;
 use crate::vector_based::VectorBasedDataStructure;
+use std::cmp::Ordering;
+use std::fmt::{self, Debug};
+use std::iter::{FromIterator, Rev};
+use std::{slice, vec};
 
+/// Array-backed stack. Iteration, formatting and ordering all go from the top down.
+#[derive(Clone, PartialEq, Eq, Hash)]
 pub struct Stack<T> {
     entry: Vec<T>,
 }
@@ -26,6 +33,109 @@ impl<T> Stack<T> {
             self.entry.get(entry_len - 1)
         }
     }
+
+    pub fn peek_mut(&mut self) -> Option<&mut T> {
+        self.entry.last_mut()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entry.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entry.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.entry.clear();
+    }
+
+    pub fn iter(&self) -> Rev<slice::Iter<'_, T>> {
+        self.entry.iter().rev()
+    }
+}
+
+impl<T> StackLike<T> for Stack<T> {
+    fn push(&mut self, element: T) {
+        self.push(element);
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        self.pop()
+    }
+
+    fn peek(&self) -> Option<&T> {
+        self.peek()
+    }
+
+    fn peek_mut(&mut self) -> Option<&mut T> {
+        self.peek_mut()
+    }
+
+    fn len(&self) -> usize {
+        self.len()
+    }
+
+    fn clear(&mut self) {
+        self.clear();
+    }
+}
+
+impl<T> Default for Stack<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Debug> Debug for Stack<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: PartialOrd> PartialOrd for Stack<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.iter().partial_cmp(other.iter())
+    }
+}
+
+impl<T: Ord> Ord for Stack<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.iter().cmp(other.iter())
+    }
+}
+
+/// Pushes the elements in order, so the last one ends up on top.
+impl<T> FromIterator<T> for Stack<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Stack {
+            entry: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl<T> Extend<T> for Stack<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.entry.extend(iter);
+    }
+}
+
+impl<T> IntoIterator for Stack<T> {
+    type Item = T;
+    type IntoIter = Rev<vec::IntoIter<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.entry.into_iter().rev()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a Stack<T> {
+    type Item = &'a T;
+    type IntoIter = Rev<slice::Iter<'a, T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
 }
 
 impl<T> From<Vec<T>> for Stack<T> {
@@ -49,6 +159,10 @@ impl<T> VectorBasedDataStructure<T> for Stack<T> {
         self.entry.len()
     }
 
+    fn is_empty(&self) -> bool {
+        self.entry.is_empty()
+    }
+
     fn capacity(&self) -> usize {
         self.entry.capacity()
     }
@@ -57,8 +171,11 @@ impl<T> VectorBasedDataStructure<T> for Stack<T> {
 #[cfg(test)]
 mod tests {
     use crate::stack::Stack;
+    use crate::stack_like::stack_like_tests;
     use crate::vector_based::VectorBasedDataStructure;
 
+    stack_like_tests!(stack_like, Stack<i32>);
+
     #[test]
     fn push() {
         let mut stack: Stack<i32> = Stack::new();
@@ -142,4 +259,37 @@ mod tests {
         stack.push(3);
         assert_eq!(stack.len(), 3);
     }
+
+    #[test]
+    fn iter_from_top() {
+        let stack = Stack::from(vec![1, 2, 3]);
+        assert_eq!(stack.iter().collect::<Vec<_>>(), vec![&3, &2, &1]);
+        assert_eq!((&stack).into_iter().count(), 3);
+        assert_eq!(stack.into_iter().collect::<Vec<_>>(), vec![3, 2, 1]);
+    }
+
+    #[test]
+    fn from_iter_and_extend() {
+        let mut stack: Stack<i32> = (1..=3).collect();
+        assert_eq!(stack.peek(), Some(&3));
+        stack.extend(vec![4, 5]);
+        assert_eq!(stack.pop(), Some(5));
+        assert_eq!(stack.len(), 4);
+    }
+
+    #[test]
+    fn std_traits() {
+        use std::collections::HashSet;
+
+        let stack = Stack::from(vec![1, 2, 3]);
+        let copy = stack.clone();
+        assert_eq!(stack, copy);
+        assert_eq!(format!("{:?}", stack), "[3, 2, 1]");
+        assert_eq!(Stack::<i32>::default(), Stack::new());
+        // Compared from the top down
+        assert!(Stack::from(vec![9, 1]) < Stack::from(vec![1, 2]));
+        assert!(Stack::from(vec![1]) < Stack::from(vec![0, 1]));
+        let set: HashSet<Stack<i32>> = vec![stack, copy, Stack::new()].into_iter().collect();
+        assert_eq!(set.len(), 2);
+    }
 }
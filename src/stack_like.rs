@@ -0,0 +1,110 @@
+/// LIFO operations shared by every stack in the crate, so algorithms can be generic over
+/// the backing storage.
+pub trait StackLike<T> {
+    fn push(&mut self, element: T);
+    fn pop(&mut self) -> Option<T>;
+    fn peek(&self) -> Option<&T>;
+    fn peek_mut(&mut self) -> Option<&mut T>;
+    fn len(&self) -> usize;
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    fn clear(&mut self);
+}
+
+/// Generates the shared `StackLike` test suite as a module named `$name`, run against
+/// `$stack`, which must hold `i32` and implement `Default`.
+#[cfg(test)]
+macro_rules! stack_like_tests {
+    ($name:ident, $stack:ty) => {
+        mod $name {
+            use super::*;
+            use crate::stack_like::StackLike;
+
+            fn new_stack() -> $stack {
+                <$stack>::default()
+            }
+
+            fn drain<S: StackLike<i32>>(stack: &mut S) -> Vec<i32> {
+                let mut popped = Vec::new();
+                while let Some(element) = stack.pop() {
+                    popped.push(element);
+                }
+                popped
+            }
+
+            #[test]
+            fn empty() {
+                let mut stack = new_stack();
+                assert!(StackLike::is_empty(&stack));
+                assert_eq!(StackLike::len(&stack), 0);
+                assert_eq!(StackLike::peek(&stack), None);
+                assert_eq!(StackLike::peek_mut(&mut stack), None);
+                assert_eq!(StackLike::pop(&mut stack), None);
+            }
+
+            #[test]
+            fn last_in_first_out() {
+                let mut stack = new_stack();
+                for i in 0..5 {
+                    StackLike::push(&mut stack, i);
+                    assert_eq!(StackLike::peek(&stack), Some(&i));
+                    assert_eq!(StackLike::len(&stack), i as usize + 1);
+                }
+                assert_eq!(drain(&mut stack), vec![4, 3, 2, 1, 0]);
+                assert!(StackLike::is_empty(&stack));
+            }
+
+            #[test]
+            fn interleaved_push_and_pop() {
+                let mut stack = new_stack();
+                StackLike::push(&mut stack, 1);
+                StackLike::push(&mut stack, 2);
+                assert_eq!(StackLike::pop(&mut stack), Some(2));
+                StackLike::push(&mut stack, 3);
+                assert_eq!(StackLike::pop(&mut stack), Some(3));
+                assert_eq!(StackLike::pop(&mut stack), Some(1));
+                assert_eq!(StackLike::pop(&mut stack), None);
+                StackLike::push(&mut stack, 4);
+                assert_eq!(StackLike::len(&stack), 1);
+            }
+
+            #[test]
+            fn peek_mut() {
+                let mut stack = new_stack();
+                StackLike::push(&mut stack, 1);
+                StackLike::push(&mut stack, 2);
+                *StackLike::peek_mut(&mut stack).unwrap() *= 10;
+                assert_eq!(StackLike::peek(&stack), Some(&20));
+                assert_eq!(StackLike::len(&stack), 2);
+                assert_eq!(drain(&mut stack), vec![20, 1]);
+            }
+
+            #[test]
+            fn clear() {
+                let mut stack = new_stack();
+                for i in 0..100 {
+                    StackLike::push(&mut stack, i);
+                }
+                StackLike::clear(&mut stack);
+                assert!(StackLike::is_empty(&stack));
+                assert_eq!(StackLike::peek(&stack), None);
+                StackLike::push(&mut stack, 7);
+                assert_eq!(drain(&mut stack), vec![7]);
+            }
+
+            #[test]
+            fn drop_deep_stack() {
+                crate::test_util::on_small_stack(|| {
+                    let mut stack = new_stack();
+                    for i in 0..crate::test_util::DEEP {
+                        StackLike::push(&mut stack, i);
+                    }
+                });
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+pub(crate) use stack_like_tests;
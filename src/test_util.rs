@@ -0,0 +1,14 @@
+/// Element count for the tests that build very long linked structures, reduced under Miri
+/// so that it finishes in reasonable time.
+pub(crate) const DEEP: i32 = if cfg!(miri) { 1_000 } else { 1_000_000 };
+
+/// Runs `test` on a thread with a 128 KiB stack, so code that recurses once per node
+/// overflows instead of passing by luck.
+pub(crate) fn on_small_stack<F: FnOnce() + Send + 'static>(test: F) {
+    std::thread::Builder::new()
+        .stack_size(128 * 1024)
+        .spawn(test)
+        .unwrap()
+        .join()
+        .unwrap();
+}
@@ -2,5 +2,8 @@ pub trait VectorBasedDataStructure<T> {
     fn new() -> Self;
     fn with_capacity(capacity: usize) -> Self;
     fn len(&self) -> usize;
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
     fn capacity(&self) -> usize;
 }